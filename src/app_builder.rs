@@ -1,20 +1,98 @@
-use std::collections::BTreeMap;
+use std::{
+	collections::BTreeMap,
+	path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
 
 use crate::{
-	configuration::{Configuration, LanguageType, ServiceType, StoredType},
+	configuration::{
+		AccentType, CaseType, Cli, Command, Configuration, LanguageType, ModeType, ServiceType,
+		StoreLockType, StoredType, TieBreakType, WriteInType,
+	},
 	domain::{
-		generic_domains::{AttendenceSheet, Candidate, Score},
+		candidate_profile::CandidateProfile,
+		election_mode::ElectionMode,
+		election_state::ElectionState,
+		electoral_roll::ElectoralRoll,
+		generic_domains::{AttendenceSheet, Candidate, QuestionId, Score, Voter},
+		identity::{AccentPolicy, CasePolicy, IdentityPolicy},
+		schedule::Schedule,
 		scoreboard::Scoreboard,
+		tie_break::{BirthDate, TieBreakPolicy},
 		voting_machine::VotingMachine,
+		voting_session::VotingSession,
 	},
-	interfaces::lexicon::Lexicon,
+	interfaces::{cli_interfaces::show_recount, lexicon::Lexicon},
 	service::Service,
 	services::{stdio::StdioService, tcp::TcpService, udp::UdpService, web::WebService},
-	storage::Storage,
+	storage::{LockMode, Storage, StoreOptions},
 	storages::{file::FileStore, memory::MemoryStore},
 	use_cases::VotingController,
 };
 
+/// # Errors
+///
+/// Will return `Err` if the machine or the offline command fails
+pub async fn run_cli(cli: Cli) -> anyhow::Result<()> {
+	match (cli.command, cli.configuration) {
+		(Some(Command::Recount { path, language }), _) => recount_file(&path, language).await,
+		(None, Some(configuration)) => run_app(configuration).await,
+		(None, None) => bail!("expected a command or a configuration"),
+	}
+}
+
+/// Recounts every question of the file store at `path`, without running the
+/// machine. The ballots and the scores are both read as stored after the last
+/// event, rather than rebuilt from the events, so that each checks the other.
+///
+/// # Errors
+///
+/// Will return `Err` if the file cannot be read, was written before ballots
+/// were stored or keeps no session after its last event, or if a recount does
+/// not match the stored scores
+pub async fn recount_file(path: &Path, language: LanguageType) -> anyhow::Result<()> {
+	let lexicon = match language {
+		LanguageType::Fr => Lexicon::french(),
+		LanguageType::En => Lexicon::english(),
+	};
+	if !path.exists() {
+		bail!("{} does not exist", path.display());
+	}
+	let filepath = path
+		.to_str()
+		.with_context(|| format!("{} is not a valid path", path.display()))?;
+	let store = FileStore::create(VotingSession::default(), filepath).await?;
+	if !store.keeps_ballots().await? {
+		bail!(
+			"{} was written before ballots were stored and cannot be recounted",
+			path.display()
+		);
+	}
+	let events = store.get_chain().await?.len();
+	let snapshot = store.get_snapshot(events).await?;
+	if snapshot.events < events {
+		bail!(
+			"{} keeps no session after its last event to recount",
+			path.display()
+		);
+	}
+	let mut matches = true;
+	for (question, machine) in snapshot.session.get_questions() {
+		let recount = machine.recount();
+		matches &= recount.discrepancies.is_empty();
+		println!("{question}: {}", show_recount(&recount, &lexicon));
+	}
+	if !matches {
+		bail!(
+			"the recount of {} does not match its scores",
+			path.display()
+		);
+	}
+	Ok(())
+}
+
 /// # Errors
 ///
 /// Will return `Err` if `handle_lines` exits with an error
@@ -36,38 +114,453 @@ pub async fn dispatch_service<Store: Storage + Send + Sync + Clone + 'static>(
 	}
 }
 
+/// Reads an electoral roll file holding one voter per line.
+///
 /// # Errors
 ///
-/// Will return `Err` if `handle_line` exits with an error
-pub async fn handle_lines<Store: Storage, Serv: Service<Store>>(
-	configuration: Configuration,
-) -> anyhow::Result<()> {
-	let mut tableau_candidats = BTreeMap::new();
+/// Will return `Err` if the file cannot be read
+pub async fn load_electoral_roll(path: &Path) -> anyhow::Result<ElectoralRoll> {
+	let content = tokio::fs::read_to_string(path)
+		.await
+		.with_context(|| format!("cannot read the electoral roll {}", path.display()))?;
+	Ok(ElectoralRoll(
+		content
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty())
+			.map(|line| Voter(line.to_string()))
+			.collect(),
+	))
+}
 
-	for candidates in configuration.candidates {
-		let candidate = Candidate(candidates);
-		tableau_candidats.insert(candidate, Score::default());
+#[derive(Deserialize)]
+struct CandidateProfileFile {
+	display_name: String,
+	#[serde(default)]
+	party: Option<String>,
+	#[serde(default)]
+	statement: Option<String>,
+	#[serde(default)]
+	image: Option<String>,
+}
+
+/// Reads a JSON file mapping each candidate id to its profile.
+///
+/// # Errors
+///
+/// Will return `Err` if the file cannot be read or parsed
+pub async fn load_candidate_profiles(
+	path: &Path,
+) -> anyhow::Result<BTreeMap<Candidate, CandidateProfile>> {
+	let content = tokio::fs::read_to_string(path)
+		.await
+		.with_context(|| format!("cannot read the candidate profiles {}", path.display()))?;
+	let profiles: BTreeMap<String, CandidateProfileFile> = serde_json::from_str(&content)
+		.with_context(|| format!("invalid candidate profiles in {}", path.display()))?;
+	Ok(profiles
+		.into_iter()
+		.map(|(candidate, profile)| {
+			let profile = CandidateProfile {
+				display_name: profile.display_name,
+				party: profile.party,
+				statement: profile.statement,
+				image: profile.image,
+			};
+			(Candidate(candidate), profile)
+		})
+		.collect())
+}
+
+/// Returns the file the named store of `election` is kept in, under `directory`.
+///
+/// # Errors
+///
+/// Will return `Err` if the election name is not fit for a file name
+pub fn election_file(directory: &Path, election: &str) -> anyhow::Result<PathBuf> {
+	if election.is_empty()
+		|| !election
+			.chars()
+			.all(|character| character.is_ascii_alphanumeric() || "-_".contains(character))
+	{
+		bail!("an election name holds letters, digits, `-` and `_` only, got {election}");
 	}
+	Ok(directory.join(format!("{election}.json")))
+}
 
-	let lexicon = match configuration.language {
-		LanguageType::Fr => Lexicon::french(),
-		LanguageType::En => Lexicon::english(),
+/// Returns the file the file store is kept in: the named store of the
+/// election when `--election` is given, `--store-path` being its directory,
+/// otherwise `--store-path` itself if set.
+///
+/// # Errors
+///
+/// Will return `Err` if the election name is not fit for a file name, if
+/// `--store-path` is a directory while no election is named, or if the
+/// directory of the named stores cannot be created
+pub async fn store_path(configuration: &Configuration) -> anyhow::Result<Option<PathBuf>> {
+	let Some(election) = &configuration.election else {
+		if let Some(path) = configuration
+			.store_path
+			.as_ref()
+			.filter(|path| path.is_dir())
+		{
+			bail!(
+				"{} is a directory; name the election to store in it with `--election`",
+				path.display()
+			);
+		}
+		return Ok(configuration.store_path.clone());
 	};
+	let directory = configuration
+		.store_path
+		.clone()
+		.unwrap_or_else(|| PathBuf::from("."));
+	let file = election_file(&directory, election)?;
+	tokio::fs::create_dir_all(&directory)
+		.await
+		.with_context(|| format!("cannot create the store directory {}", directory.display()))?;
+	Ok(Some(file))
+}
+
+/// Parses a `--question` argument of the form `<id>=<option>,<option>`.
+///
+/// # Errors
+///
+/// Will return `Err` if the argument has no `=` or no option
+pub fn parse_question(argument: &str) -> anyhow::Result<(QuestionId, Vec<Candidate>)> {
+	let (question, options) = argument
+		.split_once('=')
+		.with_context(|| format!("expected <id>=<option>,<option>, got {argument}"))?;
+	let options: Vec<Candidate> = options
+		.split(',')
+		.map(str::trim)
+		.filter(|option| !option.is_empty())
+		.map(|option| Candidate(option.to_string()))
+		.collect();
+	if question.is_empty() || options.is_empty() {
+		bail!("expected <id>=<option>,<option>, got {argument}");
+	}
+	Ok((QuestionId(question.to_string()), options))
+}
+
+/// Parses a `--birth-date` argument of the form `<candidate>=<YYYY-MM-DD>`.
+///
+/// # Errors
+///
+/// Will return `Err` if the argument has no `=` or an invalid date
+pub fn parse_birth_date(argument: &str) -> anyhow::Result<(Candidate, BirthDate)> {
+	let (candidate, birth_date) = argument
+		.split_once('=')
+		.with_context(|| format!("expected <candidate>=<YYYY-MM-DD>, got {argument}"))?;
+	Ok((Candidate(candidate.to_string()), birth_date.parse()?))
+}
+
+/// Checks that the election opens before it closes, and that a closing time
+/// does not come with the runoff tie-break: a tie found when the election
+/// closes on its own would start a round whose ballots all come too late.
+///
+/// # Errors
+///
+/// Will return `Err` if the schedule cannot be kept
+pub fn check_schedule(configuration: &Configuration) -> anyhow::Result<()> {
+	if let (Some(opens), Some(closes)) = (configuration.opens, configuration.closes) {
+		if opens >= closes {
+			bail!("the election must open before it closes, got {opens} and {closes}");
+		}
+	}
+	if configuration.closes.is_some() && matches!(configuration.tie_break, TieBreakType::Runoff) {
+		bail!(
+			"a runoff would start after `--closes` and take no ballot; pick another `--tie-break`"
+		);
+	}
+	Ok(())
+}
+
+/// # Errors
+///
+/// Will return `Err` if a birth date cannot be parsed
+pub fn tie_break_policy(configuration: &Configuration) -> anyhow::Result<TieBreakPolicy> {
+	Ok(match configuration.tie_break {
+		TieBreakType::Unresolved => TieBreakPolicy::Unresolved,
+		TieBreakType::Oldest => TieBreakPolicy::Oldest(
+			configuration
+				.birth_dates
+				.iter()
+				.map(|argument| parse_birth_date(argument))
+				.collect::<anyhow::Result<_>>()?,
+		),
+		TieBreakType::Draw => TieBreakPolicy::Draw(configuration.seed),
+		TieBreakType::Runoff => TieBreakPolicy::Runoff,
+	})
+}
+
+fn new_voting_machine(
+	candidates: Vec<Candidate>,
+	configuration: &Configuration,
+	roll: Option<&ElectoralRoll>,
+	tie_break: &TieBreakPolicy,
+	profiles: &BTreeMap<Candidate, CandidateProfile>,
+) -> VotingMachine {
+	let mut tableau_candidats = BTreeMap::new();
+	let mut tableau_profils = BTreeMap::new();
+
+	for candidate in candidates {
+		if let Some(profile) = profiles.get(&candidate) {
+			tableau_profils.insert(candidate.clone(), profile.clone());
+		}
+		tableau_candidats.insert(candidate, Score::default());
+	}
 
 	let scoreboard = Scoreboard {
 		scores: tableau_candidats,
 		blank_score: Score::default(),
 		invalid_score: Score::default(),
+		profiles: tableau_profils,
 	};
 
 	let voters = AttendenceSheet::default();
 
-	let voting_machine = VotingMachine::new(voters, scoreboard);
+	let mode = match configuration.mode {
+		ModeType::Plurality => ElectionMode::Plurality,
+		ModeType::TwoRound => ElectionMode::TwoRound,
+		ModeType::InstantRunoff => ElectionMode::InstantRunoff,
+		ModeType::Approval => ElectionMode::Approval,
+		ModeType::Schulze => ElectionMode::Schulze,
+		ModeType::Stv => ElectionMode::SingleTransferableVote {
+			seats: configuration.seats.into(),
+		},
+	};
+
+	let voting_machine = VotingMachine::new(voters, scoreboard)
+		.with_mode(mode)
+		.with_state(ElectionState::NotOpen)
+		.with_max_proxies(configuration.max_proxies)
+		.with_tie_break(tie_break.clone())
+		.with_revotes(configuration.allow_revote)
+		.with_secret(configuration.secret)
+		.with_write_ins(matches!(configuration.write_ins, WriteInType::Accepted))
+		.with_schedule(Schedule {
+			opens: configuration.opens,
+			closes: configuration.closes,
+		})
+		.with_identity(IdentityPolicy {
+			case: match configuration.case {
+				CaseType::Sensitive => CasePolicy::Sensitive,
+				CaseType::Insensitive => CasePolicy::Insensitive,
+			},
+			accents: match configuration.accents {
+				AccentType::Kept => AccentPolicy::Kept,
+				AccentType::Stripped => AccentPolicy::Stripped,
+			},
+		});
+
+	let voting_machine = match roll {
+		Some(roll) => voting_machine.with_roll(roll.clone()),
+		None => voting_machine,
+	};
+	voting_machine.normalized()
+}
+
+/// # Errors
+///
+/// Will return `Err` if `handle_line` exits with an error
+pub async fn handle_lines<Store: Storage, Serv: Service<Store>>(
+	configuration: Configuration,
+) -> anyhow::Result<()> {
+	let lexicon = match configuration.language {
+		LanguageType::Fr => Lexicon::french(),
+		LanguageType::En => Lexicon::english(),
+	};
+
+	let roll = match &configuration.roll {
+		Some(path) => Some(load_electoral_roll(path).await?),
+		None => None,
+	};
+
+	let tie_break = tie_break_policy(&configuration)?;
+	check_schedule(&configuration)?;
+
+	let profiles = match &configuration.profiles {
+		Some(path) => load_candidate_profiles(path).await?,
+		None => BTreeMap::new(),
+	};
 
-	let memory = Store::new(voting_machine).await?;
-	let controller = VotingController::new(memory);
+	let candidates = configuration
+		.candidates
+		.iter()
+		.map(|candidate| Candidate(candidate.clone()))
+		.collect();
+	let mut voting_session = VotingSession::new(new_voting_machine(
+		candidates,
+		&configuration,
+		roll.as_ref(),
+		&tie_break,
+		&profiles,
+	));
+
+	for argument in &configuration.questions {
+		let (question, options) = parse_question(argument)?;
+		if voting_session.get_question(&question).is_some() {
+			bail!("question {question} is defined twice");
+		}
+		let machine = new_voting_machine(
+			options,
+			&configuration,
+			roll.as_ref(),
+			&tie_break,
+			&profiles,
+		);
+		voting_session = voting_session.with_question(question, machine);
+	}
+
+	let options = StoreOptions {
+		lock: match configuration.store_lock {
+			StoreLockType::Exclusive => LockMode::Exclusive,
+			StoreLockType::Shared => LockMode::Shared,
+		},
+		path: match configuration.storage {
+			StoredType::File => store_path(&configuration).await?,
+			StoredType::Memory => None,
+		},
+	};
+	let memory = Store::open(voting_session, options).await?;
+	let controller =
+		VotingController::new(memory).with_snapshot_every(configuration.snapshot_every);
 
 	Serv::new(configuration.port, lexicon, controller)
 		.serve()
 		.await
 }
+
+#[cfg(test)]
+mod tests {
+	use std::path::{Path, PathBuf};
+
+	use clap::Parser;
+
+	use crate::{
+		app_builder::{check_schedule, election_file, recount_file, store_path},
+		configuration::{Configuration, LanguageType},
+		domain::{
+			generic_domains::{AttendenceSheet, Candidate},
+			scoreboard::Scoreboard,
+			voting_machine::VotingMachine,
+		},
+		storages::file::FileStore,
+		use_cases::{VoteForm, VotingController},
+	};
+
+	fn configuration(store_arguments: &[&str]) -> Configuration {
+		let arguments = [
+			"rust_moment",
+			"-c",
+			"A",
+			"-s",
+			"file",
+			"-l",
+			"en",
+			"--service",
+			"stdio",
+			"-p",
+			"0",
+		];
+		Configuration::parse_from(arguments.iter().chain(store_arguments))
+	}
+
+	#[test]
+	fn election_names() {
+		let directory = Path::new("stores");
+		assert_eq!(
+			PathBuf::from("stores/board-2026_a.json"),
+			election_file(directory, "board-2026_a").unwrap()
+		);
+		for election in ["", "../board", "a/b", "a b", "board.json"] {
+			assert!(election_file(directory, election).is_err());
+		}
+	}
+
+	#[test]
+	fn refuses_runoffs_after_the_deadline() {
+		let closing = ["--closes", "2026-06-01T18:00:00"];
+		assert!(check_schedule(&configuration(&closing)).is_ok());
+		let runoff = [closing.as_slice(), &["--tie-break", "runoff"]].concat();
+		assert!(check_schedule(&configuration(&runoff)).is_err());
+		let backwards = [closing.as_slice(), &["--opens", "2026-06-01T19:00:00"]].concat();
+		assert!(check_schedule(&configuration(&backwards)).is_err());
+	}
+
+	#[tokio::test]
+	async fn named_store_layout() {
+		let directory = "test_named_stores";
+
+		let named = store_path(&configuration(&[
+			"--store-path",
+			directory,
+			"--election",
+			"board",
+		]))
+		.await;
+		let created = Path::new(directory).is_dir();
+		let unnamed = store_path(&configuration(&["--store-path", directory])).await;
+		let file = store_path(&configuration(&["--store-path", "test_store.json"])).await;
+		let default = store_path(&configuration(&[])).await;
+		std::fs::remove_dir(directory).unwrap();
+
+		assert_eq!(
+			Some(Path::new(directory).join("board.json")),
+			named.unwrap()
+		);
+		assert!(created);
+		assert!(unnamed.unwrap_err().to_string().contains("--election"));
+		assert_eq!(Some(PathBuf::from("test_store.json")), file.unwrap());
+		assert_eq!(None, default.unwrap());
+	}
+
+	#[tokio::test]
+	async fn recounts_stored_ballots() {
+		let filepath = "test_recount.json";
+		let voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![Candidate("A".to_string()), Candidate("B".to_string())]),
+		);
+		let store = FileStore::create(voting_machine.into(), filepath)
+			.await
+			.unwrap();
+		let controller = VotingController::new(store);
+		for (voter, candidate) in [("alice", "A"), ("bob", "B")] {
+			let vote_form = VoteForm {
+				voter: voter.to_string(),
+				candidate: candidate.to_string(),
+				proxy: String::new(),
+			};
+			controller.clone().vote(vote_form).await.unwrap();
+		}
+
+		let recounted = recount_file(Path::new(filepath), LanguageType::En).await;
+		let mut stored: serde_json::Value =
+			serde_json::from_str(&std::fs::read_to_string(filepath).unwrap()).unwrap();
+		stored["current"]["session"]["questions"]["main"]["scoreboard"]["scores"]["A"] = 2.into();
+		std::fs::write(filepath, stored.to_string()).unwrap();
+		let edited = recount_file(Path::new(filepath), LanguageType::En).await;
+		stored["ballots"]["main"] = serde_json::json!([]);
+		std::fs::write(filepath, stored.to_string()).unwrap();
+		let emptied = recount_file(Path::new(filepath), LanguageType::En).await;
+		stored.as_object_mut().unwrap().remove("ballots");
+		std::fs::write(filepath, stored.to_string()).unwrap();
+		let removed = recount_file(Path::new(filepath), LanguageType::En).await;
+		std::fs::remove_file(filepath).unwrap();
+		// Written before ballots were kept, with scores only.
+		let legacy = recount_file(Path::new("machine.json"), LanguageType::En).await;
+
+		assert!(recounted.is_ok());
+		assert!(edited.unwrap_err().to_string().contains("does not match"));
+		assert!(emptied.unwrap_err().to_string().contains("does not match"));
+		assert!(removed
+			.unwrap_err()
+			.to_string()
+			.contains("without its ballots"));
+		assert!(legacy
+			.unwrap_err()
+			.to_string()
+			.contains("cannot be recounted"));
+	}
+}
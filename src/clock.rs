@@ -0,0 +1,21 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::domain::vote_log::Timestamp;
+
+/// Tells the time the controller stamps votes with and keeps the schedule by.
+pub trait Clock: Send + Sync {
+	fn now(&self) -> Timestamp;
+}
+
+/// Reads the time from the system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> Timestamp {
+		Timestamp(
+			SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map_or(0, |elapsed| elapsed.as_secs()),
+		)
+	}
+}
@@ -1,4 +1,32 @@
-use clap::{Parser, ValueEnum};
+use std::{num::NonZeroUsize, path::PathBuf};
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::domain::vote_log::Timestamp;
+
+/// Runs the voting machine, unless an offline command is given.
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+	#[command(subcommand)]
+	pub command: Option<Command>,
+
+	#[command(flatten)]
+	pub configuration: Option<Configuration>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+	/// Tallies the ballots of a file store again and compares them with its scores
+	Recount {
+		/// File written with `--storage file`
+		#[arg(default_value = "machine.json")]
+		path: PathBuf,
+
+		#[arg(short, long, default_value = "en")]
+		language: LanguageType,
+	},
+}
 
 #[derive(Debug, Parser)]
 pub struct Configuration {
@@ -8,6 +36,20 @@ pub struct Configuration {
 	#[arg(short, long, required = true)]
 	pub storage: StoredType,
 
+	/// File the store is kept in, or with `--election` the directory of the
+	/// named stores; `machine.json` by default
+	#[arg(long)]
+	pub store_path: Option<PathBuf>,
+
+	/// Name of the election, whose store is `<election>.json` in the
+	/// `--store-path` directory, apart from the other elections
+	#[arg(long)]
+	pub election: Option<String>,
+
+	/// Whether other instances may use the file store while this one runs
+	#[arg(long, default_value = "exclusive")]
+	pub store_lock: StoreLockType,
+
 	#[arg(short, long, required = true)]
 	pub language: LanguageType,
 
@@ -16,6 +58,74 @@ pub struct Configuration {
 
 	#[arg(short, long, required = true)]
 	pub port: u16,
+
+	#[arg(short, long, default_value = "plurality")]
+	pub mode: ModeType,
+
+	/// Number of seats to fill, used by the `stv` mode
+	#[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+	pub seats: u16,
+
+	/// JSON file giving the display name, party, statement and image of each candidate
+	#[arg(long)]
+	pub profiles: Option<PathBuf>,
+
+	/// File listing the registered voters, one per line
+	#[arg(long)]
+	pub roll: Option<PathBuf>,
+
+	/// Number of proxies a single voter may hold
+	#[arg(long, default_value_t = 1)]
+	pub max_proxies: usize,
+
+	/// Extra question put to the vote, as `<id>=<option>,<option>`
+	#[arg(long = "question")]
+	pub questions: Vec<String>,
+
+	/// Lets voters change their vote until the election closes
+	#[arg(long)]
+	pub allow_revote: bool,
+
+	/// Keeps the choices apart from the voters; cannot be combined with `--allow-revote`
+	#[arg(long, conflicts_with = "allow_revote")]
+	pub secret: bool,
+
+	/// Whether a name that is not a candidate spoils the ballot or enters the count
+	#[arg(long, default_value = "strict")]
+	pub write_ins: WriteInType,
+
+	/// Whether voter and candidate names differing only by case are the same person
+	#[arg(long, default_value = "sensitive")]
+	pub case: CaseType,
+
+	/// Whether voter and candidate names differing only by accents are the same person
+	#[arg(long, default_value = "kept")]
+	pub accents: AccentType,
+
+	/// How a tie for the lead is settled
+	#[arg(long, default_value = "unresolved")]
+	pub tie_break: TieBreakType,
+
+	/// Seed of the `draw` tie-break, the same seed always drawing the same candidate
+	#[arg(long, default_value_t = 0)]
+	pub seed: u64,
+
+	/// Birth date used by the `oldest` tie-break, as `<candidate>=<YYYY-MM-DD>`
+	#[arg(long = "birth-date")]
+	pub birth_dates: Vec<String>,
+
+	/// Number of recorded events between two snapshots of the store; without it,
+	/// the session is replayed from the start each time it is read
+	#[arg(long)]
+	pub snapshot_every: Option<NonZeroUsize>,
+
+	/// UTC time the election opens at on its own, as `YYYY-MM-DDTHH:MM:SS`
+	#[arg(long)]
+	pub opens: Option<Timestamp>,
+
+	/// UTC time the election closes at on its own, ballots cast later being refused
+	#[arg(long)]
+	pub closes: Option<Timestamp>,
 }
 
 #[derive(Clone, Copy, ValueEnum, Debug)]
@@ -32,8 +142,50 @@ pub enum StoredType {
 	Memory,
 }
 
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum StoreLockType {
+	Exclusive,
+	Shared,
+}
+
 #[derive(Clone, Copy, ValueEnum, Debug)]
 pub enum LanguageType {
 	Fr,
 	En,
 }
+
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum ModeType {
+	Plurality,
+	TwoRound,
+	InstantRunoff,
+	Approval,
+	Schulze,
+	Stv,
+}
+
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum WriteInType {
+	Strict,
+	Accepted,
+}
+
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum CaseType {
+	Sensitive,
+	Insensitive,
+}
+
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum AccentType {
+	Kept,
+	Stripped,
+}
+
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum TieBreakType {
+	Unresolved,
+	Oldest,
+	Draw,
+	Runoff,
+}
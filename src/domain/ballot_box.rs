@@ -0,0 +1,21 @@
+use rand::{rngs::OsRng, Rng};
+
+use super::generic_domains::Candidate;
+
+/// Anonymous ranked ballots, kept so that the runoff rounds can be computed.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct BallotBox(pub Vec<Vec<Candidate>>);
+
+impl BallotBox {
+	/// Slips `ballot` in at a random place, so the order of the box says
+	/// nothing about the order in which voters checked in.
+	pub fn shuffle_in(&mut self, ballot: Vec<Candidate>) {
+		self.0.insert(random_position(self.0.len()), ballot);
+	}
+}
+
+/// Draws a place to insert at in a list of `len` elements, either end included,
+/// from the random number generator of the operating system.
+pub(super) fn random_position(len: usize) -> usize {
+	OsRng.gen_range(0..=len)
+}
@@ -1,14 +1,89 @@
-use super::generic_domains::{Candidate, Voter};
+use std::collections::BTreeSet;
 
-#[derive(Clone, Debug)]
+use super::{
+	generic_domains::{Candidate, Voter},
+	identity::IdentityPolicy,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Choice {
+	Single(Option<Candidate>),
+	Ranked(Vec<Candidate>),
+	Approval(BTreeSet<Candidate>),
+}
+
+impl Choice {
+	/// Returns the candidates of the ballot in order of preference.
+	#[must_use]
+	pub fn preferences(self) -> Vec<Candidate> {
+		match self {
+			Self::Single(candidate) => candidate.into_iter().collect(),
+			Self::Ranked(ranking) => ranking,
+			Self::Approval(approved) => approved.into_iter().collect(),
+		}
+	}
+
+	#[must_use]
+	pub fn map_candidates(self, mut f: impl FnMut(Candidate) -> Candidate) -> Self {
+		match self {
+			Self::Single(candidate) => Self::Single(candidate.map(f)),
+			Self::Ranked(ranking) => Self::Ranked(ranking.into_iter().map(f).collect()),
+			Self::Approval(approved) => Self::Approval(approved.into_iter().map(&mut f).collect()),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BallotPaper {
 	pub voter: Voter,
-	pub candidate: Option<Candidate>,
+	pub choice: Choice,
+	/// Proxy holder casting the ballot on behalf of `voter`.
+	pub cast_by: Option<Voter>,
 }
 
 impl BallotPaper {
 	#[must_use]
 	pub const fn new(voter: Voter, candidate: Option<Candidate>) -> Self {
-		Self { voter, candidate }
+		Self {
+			voter,
+			choice: Choice::Single(candidate),
+			cast_by: None,
+		}
+	}
+
+	#[must_use]
+	pub const fn ranked(voter: Voter, ranking: Vec<Candidate>) -> Self {
+		Self {
+			voter,
+			choice: Choice::Ranked(ranking),
+			cast_by: None,
+		}
+	}
+
+	#[must_use]
+	pub const fn approval(voter: Voter, approved: BTreeSet<Candidate>) -> Self {
+		Self {
+			voter,
+			choice: Choice::Approval(approved),
+			cast_by: None,
+		}
+	}
+
+	#[must_use]
+	pub fn cast_by(mut self, holder: Voter) -> Self {
+		self.cast_by = Some(holder);
+		self
+	}
+
+	/// Writes every name on the ballot the way `identity` says.
+	#[must_use]
+	pub fn normalized(self, identity: &IdentityPolicy) -> Self {
+		Self {
+			voter: identity.voter(self.voter),
+			choice: self
+				.choice
+				.map_candidates(|candidate| identity.candidate(candidate)),
+			cast_by: self.cast_by.map(|holder| identity.voter(holder)),
+		}
 	}
 }
@@ -0,0 +1,34 @@
+use super::{election_state::ElectionState, generic_domains::Candidate};
+
+/// What happens to the ballots already cast for a withdrawn candidate.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum WithdrawalPolicy {
+	/// The ballots still count for the candidate, who takes no new votes and
+	/// cannot win. Ranked counts pass over them to the next preference.
+	#[default]
+	Keep,
+	/// The ballots become invalid.
+	Invalidate,
+	/// Ranked ballots move on to their next preference.
+	Redistribute,
+}
+
+/// A change to the list of candidates, kept in the audit log of the machine.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum AuditEntry {
+	CandidateAdded(Candidate),
+	CandidateWithdrawn(Candidate, WithdrawalPolicy),
+	CandidateRenamed(Candidate, Candidate),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CandidateOutcome {
+	Added(Candidate),
+	Withdrawn(Candidate, WithdrawalPolicy),
+	Renamed(Candidate, Candidate),
+	AlreadyExists(Candidate),
+	UnknownCandidate(Candidate),
+	/// The policy needs the ranked ballots, which this election does not keep.
+	PolicyUnavailable(WithdrawalPolicy),
+	InvalidState(ElectionState),
+}
@@ -0,0 +1,10 @@
+/// What the ballot shows about a candidate, whose identity stays the `Candidate` id.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct CandidateProfile {
+	pub display_name: String,
+	/// Party or list the candidate stands for.
+	pub party: Option<String>,
+	pub statement: Option<String>,
+	/// Path of a picture, served as is by the web ballot.
+	pub image: Option<String>,
+}
@@ -0,0 +1,46 @@
+use super::{generic_domains::Candidate, vote_outcome::VoteOutcome};
+
+/// What a ballot added to the count, kept per voter so a re-vote can take it back.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum CountedBallot {
+	Candidate(Candidate),
+	Ranked(Vec<Candidate>),
+	Approved(Vec<Candidate>),
+	Blank,
+	Invalid,
+}
+
+impl CountedBallot {
+	#[must_use]
+	pub fn map_candidates(self, mut f: impl FnMut(Candidate) -> Candidate) -> Self {
+		match self {
+			Self::Candidate(candidate) => Self::Candidate(f(candidate)),
+			Self::Ranked(ranking) => Self::Ranked(ranking.into_iter().map(f).collect()),
+			Self::Approved(approved) => Self::Approved(approved.into_iter().map(f).collect()),
+			Self::Blank => Self::Blank,
+			Self::Invalid => Self::Invalid,
+		}
+	}
+
+	/// Returns what `outcome` counted, or `None` when the ballot was refused.
+	#[must_use]
+	pub fn from_outcome(outcome: &VoteOutcome) -> Option<Self> {
+		match outcome {
+			VoteOutcome::AcceptedVote(_, candidate) => Some(Self::Candidate(candidate.clone())),
+			VoteOutcome::AcceptedRankedVote(_, ranking) => Some(Self::Ranked(ranking.clone())),
+			VoteOutcome::AcceptedApprovalVote(_, approved, _) => {
+				Some(Self::Approved(approved.clone()))
+			}
+			VoteOutcome::BlankVote(_) => Some(Self::Blank),
+			VoteOutcome::InvalidVote(_) => Some(Self::Invalid),
+			VoteOutcome::VoteChanged(outcome) => Self::from_outcome(outcome),
+			VoteOutcome::BallotCast(_)
+			| VoteOutcome::HasAlreadyVoted(_)
+			| VoteOutcome::NotRegistered(_)
+			| VoteOutcome::NotProxyHolder(_, _)
+			| VoteOutcome::ElectionNotOpen(_)
+			| VoteOutcome::ElectionClosed(_)
+			| VoteOutcome::DeadlinePassed(_) => None,
+		}
+	}
+}
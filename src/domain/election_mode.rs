@@ -0,0 +1,12 @@
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum ElectionMode {
+	#[default]
+	Plurality,
+	TwoRound,
+	InstantRunoff,
+	Approval,
+	Schulze,
+	SingleTransferableVote {
+		seats: usize,
+	},
+}
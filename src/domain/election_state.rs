@@ -0,0 +1,17 @@
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ElectionState {
+	NotOpen,
+	Open,
+	Closed,
+}
+
+use super::generic_domains::Candidate;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TransitionOutcome {
+	Opened,
+	Closed,
+	/// Closing found a tie and the tie-break policy started a new round instead.
+	Runoff(Vec<Candidate>),
+	InvalidTransition(ElectionState),
+}
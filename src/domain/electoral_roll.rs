@@ -0,0 +1,13 @@
+use std::collections::BTreeSet;
+
+use super::generic_domains::Voter;
+
+/// Voters allowed to take part in the election.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct ElectoralRoll(pub BTreeSet<Voter>);
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Turnout {
+	pub voted: usize,
+	pub registered: usize,
+}
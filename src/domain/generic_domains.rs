@@ -1,4 +1,7 @@
-use std::{collections::BTreeSet, fmt::Display};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt::Display,
+};
 
 #[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Debug)]
 pub struct Voter(pub String);
@@ -18,6 +21,23 @@ impl Display for Candidate {
 	}
 }
 
+#[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Debug)]
+pub struct QuestionId(pub String);
+
+impl QuestionId {
+	/// Question used when none is named.
+	#[must_use]
+	pub fn main() -> Self {
+		Self("main".to_string())
+	}
+}
+
+impl Display for QuestionId {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
 #[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Copy, Default, Debug)]
 pub struct Score(pub usize);
 
@@ -27,12 +47,14 @@ impl Display for Score {
 	}
 }
 
+/// Voters who took part, then the proxy holder who cast the ballot of each
+/// voter who did not vote in person.
 #[derive(PartialEq, Eq, Debug, Clone)]
-pub struct AttendenceSheet(pub BTreeSet<Voter>);
+pub struct AttendenceSheet(pub BTreeSet<Voter>, pub BTreeMap<Voter, Voter>);
 
 impl Default for AttendenceSheet {
 	fn default() -> Self {
 		let set: BTreeSet<Voter> = BTreeSet::new();
-		Self(set)
+		Self(set, BTreeMap::new())
 	}
 }
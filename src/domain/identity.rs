@@ -0,0 +1,68 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use super::generic_domains::{Candidate, Voter};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum CasePolicy {
+	/// `Malo` and `malo` are told apart.
+	#[default]
+	Sensitive,
+	/// Names are case folded, so that `Straße` and `STRASSE` match.
+	Insensitive,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum AccentPolicy {
+	/// `Léa` and `Lea` are told apart.
+	#[default]
+	Kept,
+	/// Accents and other combining marks are dropped.
+	Stripped,
+}
+
+/// How voter and candidate names are brought to one form, so that the same
+/// person is always known by the same name.
+///
+/// Names are always put in Unicode NFC, with their blanks trimmed and
+/// collapsed; case and accents are only dropped when the policy says so.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct IdentityPolicy {
+	pub case: CasePolicy,
+	pub accents: AccentPolicy,
+}
+
+/// Folds the case of `name` for comparison. Going through upper case first
+/// brings together what lower case alone keeps apart, such as `ß` and `ss`
+/// or `ς` and `σ`, as Unicode full case folding does.
+fn fold_case(name: &str) -> String {
+	name.to_uppercase().to_lowercase()
+}
+
+impl IdentityPolicy {
+	#[must_use]
+	pub fn normalize(&self, name: &str) -> String {
+		let name = name.split_whitespace().collect::<Vec<&str>>().join(" ");
+		let name = match self.case {
+			CasePolicy::Sensitive => name,
+			CasePolicy::Insensitive => fold_case(&name),
+		};
+		match self.accents {
+			AccentPolicy::Kept => name.nfc().collect(),
+			AccentPolicy::Stripped => name
+				.nfd()
+				.filter(|character| !is_combining_mark(*character))
+				.nfc()
+				.collect(),
+		}
+	}
+
+	#[must_use]
+	pub fn voter(&self, voter: Voter) -> Voter {
+		Voter(self.normalize(&voter.0))
+	}
+
+	#[must_use]
+	pub fn candidate(&self, candidate: Candidate) -> Candidate {
+		Candidate(self.normalize(&candidate.0))
+	}
+}
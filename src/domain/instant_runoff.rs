@@ -0,0 +1,133 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+	ballot_box::BallotBox,
+	generic_domains::{Candidate, Score},
+};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct RunoffRound {
+	pub scores: BTreeMap<Candidate, Score>,
+	pub exhausted: Score,
+	pub eliminated: Option<Candidate>,
+	/// How the candidate to eliminate was picked among those tied for last.
+	pub last_place_tie: Option<LastPlaceTie>,
+}
+
+/// What set apart the candidates tied for last place.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LastPlaceRule {
+	/// The candidate with the fewest votes in this earlier round, counted from 1.
+	EarlierRound(usize),
+	/// The candidate coming last in name order, when every earlier round is tied too.
+	NameOrder,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct LastPlaceTie {
+	pub tied: Vec<Candidate>,
+	pub settled_by: LastPlaceRule,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct InstantRunoff {
+	pub rounds: Vec<RunoffRound>,
+	pub winner: Option<Candidate>,
+}
+
+/// Picks the candidate to eliminate among those tied for `last` place,
+/// going back through the counts of the earlier `rounds` from the latest,
+/// then falling back on name order. The tie is returned when there was one.
+pub(super) fn eliminate<'a, Count: Ord + Copy + Default + 'a>(
+	last: Vec<Candidate>,
+	rounds: impl DoubleEndedIterator<Item = &'a BTreeMap<Candidate, Count>> + ExactSizeIterator,
+) -> Option<(Candidate, Option<LastPlaceTie>)> {
+	let mut remaining = last.clone();
+	let mut settled_by = LastPlaceRule::NameOrder;
+	for (number, counts) in rounds.enumerate().rev() {
+		if remaining.len() == 1 {
+			break;
+		}
+		let score = |candidate: &Candidate| counts.get(candidate).copied().unwrap_or_default();
+		let lowest = remaining.iter().map(score).min().unwrap_or_default();
+		remaining.retain(|candidate| score(candidate) == lowest);
+		if remaining.len() == 1 {
+			settled_by = LastPlaceRule::EarlierRound(number + 1);
+		}
+	}
+	let eliminated = remaining.into_iter().max()?;
+	let tie = (last.len() > 1).then_some(LastPlaceTie {
+		tied: last,
+		settled_by,
+	});
+	Some((eliminated, tie))
+}
+
+impl InstantRunoff {
+	/// Counts the ballots round by round, eliminating the last candidate until
+	/// one of them holds an absolute majority of the continuing ballots. The
+	/// count stops without a winner when every continuing candidate is tied.
+	#[must_use]
+	pub fn tally<'a>(
+		candidates: impl IntoIterator<Item = &'a Candidate>,
+		ballots: &BallotBox,
+	) -> Self {
+		let mut continuing: BTreeSet<Candidate> = candidates.into_iter().cloned().collect();
+		let mut rounds = Vec::new();
+
+		loop {
+			let mut scores: BTreeMap<Candidate, Score> = continuing
+				.iter()
+				.map(|candidate| (candidate.clone(), Score::default()))
+				.collect();
+			let mut exhausted = Score::default();
+			for ballot in &ballots.0 {
+				let preferred = ballot
+					.iter()
+					.find(|candidate| continuing.contains(*candidate));
+				match preferred.and_then(|candidate| scores.get_mut(candidate)) {
+					Some(score) => score.0 += 1,
+					None => exhausted.0 += 1,
+				}
+			}
+
+			let total: usize = scores.values().map(|score| score.0).sum();
+			let leader = scores
+				.iter()
+				.max_by_key(|(_, score)| **score)
+				.map(|(candidate, score)| (candidate.clone(), *score));
+			let lowest = scores.values().min().copied().unwrap_or_default();
+			let last: Vec<Candidate> = scores
+				.iter()
+				.filter(|(_, score)| **score == lowest)
+				.map(|(candidate, _)| candidate.clone())
+				.collect();
+
+			let winner = leader
+				.filter(|(_, score)| score.0 * 2 > total)
+				.map(|(candidate, _)| candidate);
+			let elimination = if winner.is_some() || total == 0 || last.len() == continuing.len() {
+				None
+			} else {
+				eliminate(last, rounds.iter().map(|round: &RunoffRound| &round.scores))
+			};
+			let Some((eliminated, last_place_tie)) = elimination else {
+				rounds.push(RunoffRound {
+					scores,
+					exhausted,
+					eliminated: None,
+					last_place_tie: None,
+				});
+				return Self { rounds, winner };
+			};
+
+			continuing.remove(&eliminated);
+			rounds.push(RunoffRound {
+				scores,
+				exhausted,
+				eliminated: Some(eliminated),
+				last_place_tie,
+			});
+		}
+	}
+}
@@ -1,17 +1,53 @@
+pub mod ballot_box;
 pub mod ballot_paper;
+pub mod candidate_change;
+pub mod candidate_profile;
+pub mod counted_ballot;
+pub mod election_mode;
+pub mod election_state;
+pub mod electoral_roll;
 pub mod generic_domains;
+pub mod identity;
+pub mod instant_runoff;
+pub mod proxy;
+pub mod receipt;
+pub mod recount;
+pub mod results;
+pub mod round;
+pub mod schedule;
+pub mod schulze;
 pub mod scoreboard;
+pub mod single_transferable_vote;
+pub mod tie_break;
+pub mod vote_log;
 pub mod vote_outcome;
+pub mod voting_event;
 pub mod voting_machine;
+pub mod voting_session;
 
 #[cfg(test)]
 mod tests {
 	use std::collections::BTreeMap;
 
 	use super::{
-		ballot_paper::BallotPaper,
+		ballot_paper::{BallotPaper, Choice},
+		candidate_change::{AuditEntry, CandidateOutcome, WithdrawalPolicy},
+		candidate_profile::CandidateProfile,
+		election_mode::ElectionMode,
+		election_state::{ElectionState, TransitionOutcome},
+		electoral_roll::{ElectoralRoll, Turnout},
 		generic_domains::{AttendenceSheet, Candidate, Score, Voter},
+		identity::{AccentPolicy, CasePolicy, IdentityPolicy},
+		proxy::ProxyOutcome,
+		receipt::Receipt,
+		recount::{Discrepancy, TallyLine},
+		results::{Majority, Percentage},
+		round::RoundOutcome,
 		scoreboard::Scoreboard,
+		single_transferable_vote::{Transfer, Votes},
+		tie_break::{BirthDate, TieBreak, TieBreakPolicy, TieResolution},
+		vote_log::{ChainVerification, Timestamp, VoteLog, VoteSource},
+		vote_outcome::VoteOutcome,
 		voting_machine::VotingMachine,
 	};
 
@@ -28,16 +64,14 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let mut voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let ballot_paper = BallotPaper {
-			voter: voter.clone(),
-			candidate: Some(candidate.clone()),
-		};
+		let ballot_paper = BallotPaper::new(voter.clone(), Some(candidate.clone()));
 		voting_machine.vote(ballot_paper);
 
 		let mut correct_voters = AttendenceSheet::default();
@@ -47,6 +81,7 @@ mod tests {
 			scores: correct_scores,
 			blank_score: Score(0),
 			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
 		};
 		correct_voters.0.insert(voter);
 		assert_eq!(&correct_voters, voting_machine.get_voter());
@@ -66,16 +101,14 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let mut voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let ballot_paper = BallotPaper {
-			voter: voter.clone(),
-			candidate: Some(candidate.clone()),
-		};
+		let ballot_paper = BallotPaper::new(voter.clone(), Some(candidate.clone()));
 		voting_machine.vote(ballot_paper.clone());
 		voting_machine.vote(ballot_paper);
 
@@ -87,6 +120,7 @@ mod tests {
 			scores: correct_scores,
 			blank_score: Score(0),
 			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
 		};
 		assert_eq!(&correct_voters, voting_machine.get_voter());
 		assert_eq!(&correct_scoreboard, voting_machine.get_scoreboard());
@@ -101,16 +135,14 @@ mod tests {
 			scores: BTreeMap::new(),
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let mut voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let ballot_paper = BallotPaper {
-			voter: voter.clone(),
-			candidate: Some(candidate),
-		};
+		let ballot_paper = BallotPaper::new(voter.clone(), Some(candidate));
 		voting_machine.vote(ballot_paper);
 
 		let mut correct_voters = AttendenceSheet::default();
@@ -119,6 +151,7 @@ mod tests {
 			scores: BTreeMap::new(),
 			blank_score: Score(0),
 			invalid_score: Score(1),
+			profiles: BTreeMap::new(),
 		};
 		assert_eq!(&correct_voters, voting_machine.get_voter());
 		assert_eq!(&correct_scoreboard, voting_machine.get_scoreboard());
@@ -131,16 +164,14 @@ mod tests {
 			scores: BTreeMap::new(),
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let mut voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let ballot_paper = BallotPaper {
-			voter: voter.clone(),
-			candidate: None,
-		};
+		let ballot_paper = BallotPaper::new(voter.clone(), None);
 		voting_machine.vote(ballot_paper);
 
 		let mut correct_voters = AttendenceSheet::default();
@@ -149,8 +180,1205 @@ mod tests {
 			scores: BTreeMap::new(),
 			blank_score: Score(1),
 			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
 		};
 		assert_eq!(&correct_voters, voting_machine.get_voter());
 		assert_eq!(&correct_scoreboard, voting_machine.get_scoreboard());
 	}
+
+	#[test]
+	fn ranked_vote() {
+		let voter = Voter("Malo".to_string());
+		let first = Candidate("Philipe_Poutou".to_string());
+		let second = Candidate("Nathalie_Arthaud".to_string());
+
+		let scoreboard = Scoreboard::new(vec![first.clone(), second.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::InstantRunoff);
+
+		let ranking = vec![first.clone(), second.clone()];
+		let outcome = voting_machine.vote(BallotPaper::ranked(voter.clone(), ranking.clone()));
+
+		let mut correct_scores = BTreeMap::new();
+		correct_scores.insert(first, Score(1));
+		correct_scores.insert(second, Score(0));
+		assert_eq!(
+			VoteOutcome::AcceptedRankedVote(voter, ranking.clone()),
+			outcome
+		);
+		assert_eq!(&correct_scores, &voting_machine.get_scoreboard().scores);
+		assert_eq!(vec![ranking], voting_machine.get_ballots().0);
+	}
+
+	#[test]
+	fn instant_runoff_rounds() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::InstantRunoff);
+
+		let rankings = [
+			vec![a.clone(), b.clone()],
+			vec![a.clone(), c.clone()],
+			vec![b.clone(), a.clone()],
+			vec![b.clone(), c.clone()],
+			vec![c.clone(), b.clone()],
+		];
+		for (number, ranking) in rankings.into_iter().enumerate() {
+			voting_machine.vote(BallotPaper::ranked(Voter(number.to_string()), ranking));
+		}
+
+		let runoff = voting_machine.instant_runoff();
+		assert_eq!(2, runoff.rounds.len());
+		assert_eq!(Some(c.clone()), runoff.rounds[0].eliminated);
+		assert_eq!(Score(3), runoff.rounds[1].scores[&b]);
+		assert!(!runoff.rounds[1].scores.contains_key(&c));
+		assert_eq!(Some(b), runoff.winner);
+	}
+
+	#[test]
+	fn approval_vote() {
+		let voter = Voter("Malo".to_string());
+		let first = Candidate("Philipe_Poutou".to_string());
+		let second = Candidate("Nathalie_Arthaud".to_string());
+		let unknown = Candidate("Inconnu".to_string());
+
+		let scoreboard = Scoreboard::new(vec![first.clone(), second.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::Approval);
+
+		let approved = [first.clone(), unknown.clone()].into_iter().collect();
+		let outcome = voting_machine.vote(BallotPaper::approval(voter.clone(), approved));
+
+		let mut correct_scores = BTreeMap::new();
+		correct_scores.insert(first.clone(), Score(1));
+		correct_scores.insert(second, Score(0));
+		let correct_scoreboard = Scoreboard {
+			scores: correct_scores,
+			blank_score: Score(0),
+			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
+		};
+		assert_eq!(
+			VoteOutcome::AcceptedApprovalVote(voter, vec![first], vec![unknown]),
+			outcome
+		);
+		assert_eq!(&correct_scoreboard, voting_machine.get_scoreboard());
+	}
+
+	#[test]
+	fn schulze_ranking_without_condorcet_winner() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::Schulze);
+
+		let rankings = [
+			(4, vec![a.clone(), b.clone(), c.clone()]),
+			(3, vec![b.clone(), c.clone(), a.clone()]),
+			(2, vec![c.clone(), a.clone(), b.clone()]),
+		];
+		let mut number = 0;
+		for (count, ranking) in rankings {
+			for _ in 0..count {
+				number += 1;
+				voting_machine.vote(BallotPaper::ranked(
+					Voter(number.to_string()),
+					ranking.clone(),
+				));
+			}
+		}
+
+		let schulze = voting_machine.schulze();
+		assert_eq!(Score(6), schulze.pairwise[&a][&b]);
+		assert_eq!(Score(5), schulze.pairwise[&c][&a]);
+		assert_eq!(Score(6), schulze.strongest_paths[&a][&c]);
+		assert_eq!(None, schulze.condorcet_winner);
+		assert_eq!(vec![vec![a], vec![b], vec![c]], schulze.ranking);
+	}
+
+	#[test]
+	fn single_transferable_vote_surplus() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::SingleTransferableVote { seats: 2 });
+
+		let rankings = [
+			(5, vec![a.clone(), b.clone()]),
+			(2, vec![c.clone()]),
+			(1, vec![b.clone()]),
+		];
+		let mut number = 0;
+		for (count, ranking) in rankings {
+			for _ in 0..count {
+				number += 1;
+				voting_machine.vote(BallotPaper::ranked(
+					Voter(number.to_string()),
+					ranking.clone(),
+				));
+			}
+		}
+
+		let stv = voting_machine.single_transferable_vote();
+		assert_eq!(Votes(3000), stv.quota);
+		assert_eq!(2, stv.rounds.len());
+		assert_eq!(vec![a.clone()], stv.rounds[0].elected);
+		assert_eq!(
+			vec![Transfer {
+				from: a.clone(),
+				votes: Votes(2000),
+			}],
+			stv.rounds[0].transfers
+		);
+		assert_eq!(Votes(3000), stv.rounds[1].tallies[&b]);
+		assert_eq!(vec![a, b], stv.elected);
+	}
+
+	#[test]
+	fn two_round_runoff() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::TwoRound);
+
+		for (voter, candidate) in [("1", &a), ("2", &a), ("3", &b), ("4", &c), ("5", &b)] {
+			voting_machine.vote(BallotPaper::new(
+				Voter(voter.to_string()),
+				Some(candidate.clone()),
+			));
+		}
+		let first_round = voting_machine.get_scoreboard().clone();
+
+		assert_eq!(
+			RoundOutcome::SecondRound(a.clone(), b.clone()),
+			voting_machine.close_round()
+		);
+		assert_eq!(2, voting_machine.get_round());
+		assert_eq!(
+			&Scoreboard::new(vec![a.clone(), b.clone()]),
+			voting_machine.get_scoreboard()
+		);
+		assert!(voting_machine.get_voter().0.is_empty());
+		assert_eq!(
+			first_round,
+			voting_machine.get_archived_rounds()[0].scoreboard
+		);
+
+		voting_machine.vote(BallotPaper::new(Voter("1".to_string()), Some(b.clone())));
+		voting_machine.vote(BallotPaper::new(Voter("4".to_string()), Some(c)));
+		assert_eq!(RoundOutcome::Elected(b), voting_machine.close_round());
+		assert_eq!(2, voting_machine.get_round());
+	}
+
+	#[test]
+	fn election_lifecycle() {
+		let voter = Voter("Malo".to_string());
+		let candidate = Candidate("Philipe_Poutou".to_string());
+
+		let scoreboard = Scoreboard::new(vec![candidate.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_state(ElectionState::NotOpen);
+		let ballot_paper = BallotPaper::new(voter.clone(), Some(candidate));
+
+		assert_eq!(
+			VoteOutcome::ElectionNotOpen(voter.clone()),
+			voting_machine.vote(ballot_paper.clone())
+		);
+		assert_eq!(
+			TransitionOutcome::InvalidTransition(ElectionState::NotOpen),
+			voting_machine.close()
+		);
+		assert_eq!(TransitionOutcome::Opened, voting_machine.open());
+		assert_eq!(TransitionOutcome::Closed, voting_machine.close());
+		assert_eq!(
+			TransitionOutcome::InvalidTransition(ElectionState::Closed),
+			voting_machine.open()
+		);
+		assert_eq!(
+			VoteOutcome::ElectionClosed(voter),
+			voting_machine.vote(ballot_paper)
+		);
+		assert!(voting_machine.get_voter().0.is_empty());
+	}
+
+	#[test]
+	fn vote_not_registered() {
+		let registered = Voter("Malo".to_string());
+		let intruder = Voter("Inconnu".to_string());
+		let candidate = Candidate("Philipe_Poutou".to_string());
+
+		let scoreboard = Scoreboard::new(vec![candidate.clone()]);
+		let roll = ElectoralRoll([registered.clone(), Voter("Lea".to_string())].into());
+		let mut voting_machine =
+			VotingMachine::new(AttendenceSheet::default(), scoreboard).with_roll(roll);
+
+		assert_eq!(
+			VoteOutcome::NotRegistered(intruder.clone()),
+			voting_machine.vote(BallotPaper::new(intruder, Some(candidate.clone())))
+		);
+		assert_eq!(
+			VoteOutcome::AcceptedVote(registered.clone(), candidate.clone()),
+			voting_machine.vote(BallotPaper::new(registered, Some(candidate)))
+		);
+		assert_eq!(
+			Some(Turnout {
+				voted: 1,
+				registered: 2,
+			}),
+			voting_machine.turnout()
+		);
+	}
+
+	#[test]
+	fn proxy_vote() {
+		let voter = Voter("Malo".to_string());
+		let holder = Voter("Lea".to_string());
+		let other = Voter("Tom".to_string());
+		let candidate = Candidate("Philipe_Poutou".to_string());
+
+		let scoreboard = Scoreboard::new(vec![candidate.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard);
+
+		assert_eq!(
+			ProxyOutcome::Registered(voter.clone(), holder.clone()),
+			voting_machine.register_proxy(voter.clone(), holder.clone())
+		);
+		assert_eq!(
+			ProxyOutcome::TooManyProxies(holder.clone()),
+			voting_machine.register_proxy(other.clone(), holder.clone())
+		);
+		assert_eq!(
+			VoteOutcome::NotProxyHolder(other.clone(), voter.clone()),
+			voting_machine
+				.vote(BallotPaper::new(voter.clone(), Some(candidate.clone())).cast_by(other))
+		);
+		assert_eq!(
+			VoteOutcome::AcceptedVote(voter.clone(), candidate.clone()),
+			voting_machine
+				.vote(BallotPaper::new(voter.clone(), Some(candidate)).cast_by(holder.clone()))
+		);
+
+		let mut correct_voters = AttendenceSheet::default();
+		correct_voters.0.insert(voter.clone());
+		correct_voters.1.insert(voter, holder);
+		assert_eq!(&correct_voters, voting_machine.get_voter());
+	}
+
+	#[test]
+	fn results() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+		let mut scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		scoreboard.scores.insert(a.clone(), Score(2));
+		scoreboard.scores.insert(b.clone(), Score(2));
+		scoreboard.scores.insert(c.clone(), Score(1));
+		scoreboard.blank_score = Score(1);
+
+		let results = scoreboard.results();
+		let ranks: Vec<(usize, Candidate)> = results
+			.ranking
+			.iter()
+			.map(|result| (result.rank, result.candidate.clone()))
+			.collect();
+		assert_eq!(vec![(1, a.clone()), (1, b.clone()), (3, c.clone())], ranks);
+		assert_eq!(Percentage(4000), results.ranking[0].of_valid);
+		assert_eq!(Percentage(3333), results.ranking[0].of_cast);
+		assert_eq!(Majority::Tie(vec![a.clone(), b.clone()]), results.majority);
+
+		scoreboard.scores.insert(b.clone(), Score(1));
+		assert_eq!(Majority::Relative(a.clone()), scoreboard.results().majority);
+
+		scoreboard.scores.insert(c, Score(0));
+		assert_eq!(Majority::Absolute(a), scoreboard.results().majority);
+
+		assert_eq!(
+			Majority::NoValidVote,
+			Scoreboard::new(vec![b]).results().majority
+		);
+	}
+
+	fn tied_machine(tie_break: TieBreakPolicy) -> VotingMachine {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let mut voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone()]),
+		)
+		.with_tie_break(tie_break);
+		voting_machine.vote(BallotPaper::new(Voter("x".to_string()), Some(a)));
+		voting_machine.vote(BallotPaper::new(Voter("y".to_string()), Some(b)));
+		voting_machine
+	}
+
+	#[test]
+	fn tie_break_oldest() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let birth_dates = BTreeMap::from([
+			(a.clone(), "1990-05-01".parse::<BirthDate>().unwrap()),
+			(b.clone(), "1961-12-24".parse::<BirthDate>().unwrap()),
+		]);
+		let mut voting_machine = tied_machine(TieBreakPolicy::Oldest(birth_dates));
+
+		assert_eq!(TransitionOutcome::Closed, voting_machine.close());
+		assert_eq!(
+			&[TieBreak {
+				tied: vec![a, b.clone()],
+				resolution: TieResolution::Winner(b),
+			}],
+			voting_machine.get_tie_breaks()
+		);
+	}
+
+	#[test]
+	fn tie_break_draw_is_reproducible() {
+		let draws: Vec<TieBreak> = (0..2)
+			.map(|_| {
+				let mut voting_machine = tied_machine(TieBreakPolicy::Draw(42));
+				voting_machine.close();
+				voting_machine.get_tie_breaks()[0].clone()
+			})
+			.collect();
+
+		assert!(matches!(draws[0].resolution, TieResolution::Winner(_)));
+		assert_eq!(draws[0], draws[1]);
+	}
+
+	#[test]
+	fn tie_break_runoff() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let mut voting_machine = tied_machine(TieBreakPolicy::Runoff);
+
+		assert_eq!(
+			TransitionOutcome::Runoff(vec![a.clone(), b.clone()]),
+			voting_machine.close()
+		);
+		assert_eq!(ElectionState::Open, voting_machine.get_state());
+		assert_eq!(2, voting_machine.get_round());
+		assert_eq!(
+			VoteOutcome::AcceptedVote(Voter("x".to_string()), a.clone()),
+			voting_machine.vote(BallotPaper::new(Voter("x".to_string()), Some(a)))
+		);
+		assert_eq!(TransitionOutcome::Closed, voting_machine.close());
+	}
+
+	#[test]
+	fn tie_left_unresolved() {
+		let mut voting_machine = tied_machine(TieBreakPolicy::Unresolved);
+
+		assert_eq!(TransitionOutcome::Closed, voting_machine.close());
+		assert!(voting_machine.get_tie_breaks().is_empty());
+	}
+
+	#[test]
+	fn change_vote() {
+		let voter = Voter("Malo".to_string());
+		let first = Candidate("A".to_string());
+		let second = Candidate("B".to_string());
+		let mut voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![first.clone(), second.clone()]),
+		)
+		.with_revotes(true);
+
+		voting_machine.vote(BallotPaper::new(voter.clone(), Some(first.clone())));
+		assert_eq!(
+			VoteOutcome::VoteChanged(Box::new(VoteOutcome::AcceptedVote(
+				voter.clone(),
+				second.clone()
+			))),
+			voting_machine.vote(BallotPaper::new(voter.clone(), Some(second.clone())))
+		);
+		assert_eq!(Score(0), voting_machine.get_scoreboard().scores[&first]);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().scores[&second]);
+
+		voting_machine.vote(BallotPaper::new(voter.clone(), None));
+		assert_eq!(Score(0), voting_machine.get_scoreboard().scores[&second]);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().blank_score);
+
+		voting_machine.close();
+		assert_eq!(
+			VoteOutcome::ElectionClosed(voter.clone()),
+			voting_machine.vote(BallotPaper::new(voter, Some(first)))
+		);
+	}
+
+	#[test]
+	fn change_ranked_vote() {
+		let voter = Voter("Malo".to_string());
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let mut voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone()]),
+		)
+		.with_mode(ElectionMode::InstantRunoff)
+		.with_revotes(true);
+
+		voting_machine.vote(BallotPaper::ranked(
+			voter.clone(),
+			vec![a.clone(), b.clone()],
+		));
+		voting_machine.vote(BallotPaper::ranked(voter, vec![b.clone(), a.clone()]));
+
+		assert_eq!(
+			&vec![vec![b.clone(), a.clone()]],
+			&voting_machine.get_ballots().0
+		);
+		assert_eq!(Score(0), voting_machine.get_scoreboard().scores[&a]);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().scores[&b]);
+	}
+
+	#[test]
+	fn secret_ballot() {
+		let voter = Voter("Malo".to_string());
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let mut voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone()]),
+		)
+		.with_mode(ElectionMode::InstantRunoff)
+		.with_revotes(true)
+		.with_secret(true);
+
+		assert_eq!(
+			VoteOutcome::BallotCast(voter.clone()),
+			voting_machine.vote(BallotPaper::ranked(
+				voter.clone(),
+				vec![a.clone(), b.clone()]
+			))
+		);
+		assert_eq!(
+			VoteOutcome::BallotCast(Voter("Lea".to_string())),
+			voting_machine.vote(BallotPaper::new(Voter("Lea".to_string()), None))
+		);
+		assert_eq!(
+			VoteOutcome::HasAlreadyVoted(voter.clone()),
+			voting_machine.vote(BallotPaper::ranked(voter, vec![b, a.clone()]))
+		);
+		assert!(voting_machine.get_counted().is_empty());
+		assert_eq!(Score(1), voting_machine.get_scoreboard().scores[&a]);
+		assert_eq!(1, voting_machine.get_ballots().0.len());
+	}
+
+	#[test]
+	fn add_candidate_before_opening() {
+		let a = Candidate("A".to_string());
+		let mut voting_machine =
+			VotingMachine::new(AttendenceSheet::default(), Scoreboard::new(Vec::new()))
+				.with_state(ElectionState::NotOpen);
+
+		assert_eq!(
+			CandidateOutcome::Added(a.clone()),
+			voting_machine.add_candidate(a.clone())
+		);
+		assert_eq!(
+			CandidateOutcome::AlreadyExists(a.clone()),
+			voting_machine.add_candidate(a.clone())
+		);
+		voting_machine.open();
+		assert_eq!(
+			CandidateOutcome::InvalidState(ElectionState::Open),
+			voting_machine.add_candidate(Candidate("B".to_string()))
+		);
+		assert_eq!(&[AuditEntry::CandidateAdded(a)], voting_machine.get_audit());
+	}
+
+	#[test]
+	fn withdraw_candidate() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let voter = Voter("Malo".to_string());
+		let new_machine = || {
+			let mut voting_machine = VotingMachine::new(
+				AttendenceSheet::default(),
+				Scoreboard::new(vec![a.clone(), b.clone()]),
+			);
+			voting_machine.vote(BallotPaper::new(voter.clone(), Some(a.clone())));
+			voting_machine
+		};
+
+		let mut voting_machine = new_machine();
+		voting_machine.withdraw_candidate(a.clone(), WithdrawalPolicy::Keep);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().scores[&a]);
+		assert_eq!(
+			VoteOutcome::InvalidVote(Voter("Lea".to_string())),
+			voting_machine.vote(BallotPaper::new(Voter("Lea".to_string()), Some(a.clone())))
+		);
+
+		let mut voting_machine = new_machine();
+		voting_machine.withdraw_candidate(a.clone(), WithdrawalPolicy::Invalidate);
+		assert!(!voting_machine.get_scoreboard().scores.contains_key(&a));
+		assert_eq!(Score(1), voting_machine.get_scoreboard().invalid_score);
+
+		let mut voting_machine = new_machine();
+		assert_eq!(
+			CandidateOutcome::PolicyUnavailable(WithdrawalPolicy::Redistribute),
+			voting_machine.withdraw_candidate(a, WithdrawalPolicy::Redistribute)
+		);
+	}
+
+	#[test]
+	fn withdraw_ranked_candidate() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+		let mut voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]),
+		)
+		.with_mode(ElectionMode::InstantRunoff);
+		voting_machine.vote(BallotPaper::ranked(
+			Voter("x".to_string()),
+			vec![a.clone(), b.clone()],
+		));
+		voting_machine.vote(BallotPaper::ranked(
+			Voter("y".to_string()),
+			vec![c.clone(), a.clone()],
+		));
+		voting_machine.vote(BallotPaper::new(Voter("z".to_string()), Some(a.clone())));
+
+		assert_eq!(
+			CandidateOutcome::Withdrawn(a.clone(), WithdrawalPolicy::Redistribute),
+			voting_machine.withdraw_candidate(a, WithdrawalPolicy::Redistribute)
+		);
+		assert_eq!(
+			&vec![vec![b.clone()], vec![c.clone()]],
+			&voting_machine.get_ballots().0
+		);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().scores[&b]);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().scores[&c]);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().blank_score);
+	}
+
+	#[test]
+	fn rename_candidate() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let mut voting_machine =
+			VotingMachine::new(AttendenceSheet::default(), Scoreboard::new(vec![a.clone()]));
+		voting_machine.vote(BallotPaper::new(Voter("x".to_string()), Some(a.clone())));
+
+		assert_eq!(
+			CandidateOutcome::Renamed(a.clone(), b.clone()),
+			voting_machine.rename_candidate(a.clone(), b.clone())
+		);
+		assert_eq!(Score(1), voting_machine.get_scoreboard().scores[&b]);
+		assert_eq!(
+			CandidateOutcome::UnknownCandidate(a.clone()),
+			voting_machine.rename_candidate(a, b)
+		);
+	}
+
+	#[test]
+	fn candidate_profiles() {
+		let a = Candidate("a".to_string());
+		let b = Candidate("b".to_string());
+		let profile = CandidateProfile {
+			display_name: "Alice Martin".to_string(),
+			party: Some("Liste A".to_string()),
+			..CandidateProfile::default()
+		};
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone()])
+			.with_profiles(BTreeMap::from([(a.clone(), profile.clone())]));
+		assert_eq!("Alice Martin", scoreboard.display_name(&a));
+		assert_eq!("b", scoreboard.display_name(&b));
+
+		let c = Candidate("c".to_string());
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard);
+		voting_machine.rename_candidate(a, c.clone());
+		assert_eq!(
+			Some(&profile),
+			voting_machine.get_scoreboard().profiles.get(&c)
+		);
+	}
+
+	#[test]
+	fn write_in_candidates() {
+		let macos = Candidate("MacOS".to_string());
+		let linux = Candidate("linux".to_string());
+		let mut exact = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![macos.clone()]),
+		)
+		.with_write_ins(true);
+		let mut voting_machine = exact.clone().with_identity(IdentityPolicy {
+			case: CasePolicy::Insensitive,
+			accents: AccentPolicy::Kept,
+		});
+
+		assert_eq!(
+			VoteOutcome::AcceptedVote(Voter("Malo".to_string()), Candidate("macos".to_string())),
+			exact.vote(BallotPaper::new(
+				Voter("Malo".to_string()),
+				Some(Candidate("macos".to_string()))
+			))
+		);
+		assert_eq!(2, exact.get_scoreboard().scores.len());
+		assert_eq!(
+			VoteOutcome::AcceptedVote(Voter("Malo".to_string()), linux.clone()),
+			voting_machine.vote(BallotPaper::new(
+				Voter("Malo".to_string()),
+				Some(Candidate("  Linux ".to_string()))
+			))
+		);
+		assert_eq!(
+			VoteOutcome::AcceptedVote(Voter("Lea".to_string()), macos.clone()),
+			voting_machine.vote(BallotPaper::new(
+				Voter("Lea".to_string()),
+				Some(Candidate("macos".to_string()))
+			))
+		);
+		assert_eq!(
+			VoteOutcome::AcceptedVote(Voter("Ines".to_string()), linux.clone()),
+			voting_machine.vote(BallotPaper::new(
+				Voter("Ines".to_string()),
+				Some(Candidate("LINUX".to_string()))
+			))
+		);
+		let scoreboard = voting_machine.get_scoreboard();
+		assert_eq!(Score(2), scoreboard.scores[&linux]);
+		assert_eq!(Score(1), scoreboard.scores[&macos]);
+		assert_eq!(Score(0), scoreboard.invalid_score);
+	}
+
+	#[test]
+	fn write_ins_refused_by_default() {
+		let voter = Voter("Malo".to_string());
+		let mut voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![Candidate("MacOS".to_string())]),
+		);
+
+		assert_eq!(
+			VoteOutcome::InvalidVote(voter.clone()),
+			voting_machine.vote(BallotPaper::new(
+				voter,
+				Some(Candidate("Linux".to_string()))
+			))
+		);
+		assert_eq!(1, voting_machine.get_scoreboard().scores.len());
+	}
+
+	#[test]
+	fn ballot_receipts() {
+		let voter = Voter("Malo".to_string());
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let mut voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone()]),
+		)
+		.with_revotes(true);
+
+		let commit = |ballot: BallotPaper| {
+			let receipt = Receipt::commit(&ballot.voter, &ballot.choice);
+			(ballot, receipt)
+		};
+
+		let (ballot, receipt) = commit(BallotPaper::new(voter.clone(), Some(a.clone())));
+		let first = voting_machine.cast(ballot, receipt);
+		assert_eq!(
+			VoteOutcome::AcceptedVote(voter.clone(), a.clone()),
+			first.outcome
+		);
+		let first = first.receipt.unwrap();
+		assert_eq!(vec![&first], voting_machine.bulletin_board());
+
+		let (ballot, receipt) = commit(BallotPaper::new(voter.clone(), Some(b)));
+		let second = voting_machine.cast(ballot, receipt).receipt.unwrap();
+		assert_ne!(first, second);
+		assert_eq!(vec![&second], voting_machine.bulletin_board());
+
+		let (ballot, receipt) = commit(BallotPaper::new(voter.clone(), Some(a.clone())));
+		let refused = voting_machine
+			.with_state(ElectionState::Closed)
+			.cast(ballot, receipt);
+		assert_eq!(None, refused.receipt);
+
+		assert_ne!(
+			Receipt::commit(&voter, &Choice::Single(Some(a.clone()))),
+			Receipt::commit(&voter, &Choice::Single(Some(a)))
+		);
+	}
+
+	#[test]
+	fn vote_log_chain() {
+		let voter = Voter("Malo".to_string());
+		let peer = "127.0.0.1:4000".parse().unwrap();
+		let mut vote_log = VoteLog::default();
+		vote_log.append(
+			Timestamp(1_700_000_000),
+			VoteSource::Tcp(peer),
+			VoteOutcome::InvalidVote(voter.clone()),
+		);
+		vote_log.append(
+			Timestamp(1_700_000_001),
+			VoteSource::Web,
+			VoteOutcome::HasAlreadyVoted(voter.clone()),
+		);
+		vote_log.append(
+			Timestamp(1_700_000_002),
+			VoteSource::Stdio,
+			VoteOutcome::ElectionClosed(voter.clone()),
+		);
+
+		assert_eq!(VoteLog::GENESIS, vote_log.0[0].previous);
+		assert_eq!(vote_log.0[0].hash, vote_log.0[1].previous);
+		assert_eq!(ChainVerification::Intact(3), vote_log.verify());
+		assert_eq!(
+			"2023-11-14 22:13:20 UTC",
+			vote_log.0[0].timestamp.to_string()
+		);
+
+		let mut tampered = vote_log.clone();
+		tampered.0[1].outcome = VoteOutcome::BlankVote(voter.clone());
+		assert_eq!(ChainVerification::Broken(2), tampered.verify());
+
+		let mut tampered = vote_log.clone();
+		tampered.0[1].outcome = VoteOutcome::BlankVote(voter);
+		tampered.0[1].hash = tampered.0[1].digest();
+		assert_eq!(ChainVerification::Broken(3), tampered.verify());
+
+		let mut tampered = vote_log;
+		tampered.0.remove(0);
+		assert_eq!(ChainVerification::Broken(1), tampered.verify());
+	}
+
+	#[test]
+	fn parse_timestamps() {
+		let timestamp: Timestamp = "2023-11-14T22:13:20".parse().unwrap();
+		assert_eq!(Timestamp(1_700_000_000), timestamp);
+		assert_eq!(
+			Ok(timestamp),
+			"2023-11-14 22:13:20Z".parse().map_err(|_| ())
+		);
+		assert_eq!(
+			Ok(Timestamp(951_782_400)),
+			"2000-02-29T00:00:00".parse().map_err(|_| ())
+		);
+		assert_eq!(
+			"2024-03-01 12:00:00 UTC",
+			"2024-03-01T12:00:00"
+				.parse::<Timestamp>()
+				.unwrap()
+				.to_string()
+		);
+		for invalid in ["2023-11-14", "2023-11-14T24:00:00", "1969-12-31T23:59:59"] {
+			assert!(invalid.parse::<Timestamp>().is_err());
+		}
+	}
+
+	#[test]
+	fn identity_normalisation() {
+		let exact = IdentityPolicy::default();
+		let loose = IdentityPolicy {
+			case: CasePolicy::Insensitive,
+			accents: AccentPolicy::Stripped,
+		};
+		assert_eq!(
+			"L\u{e9}a Dupont",
+			exact.normalize("  Le\u{301}a \t Dupont ")
+		);
+		assert_eq!("lea dupont", loose.normalize("  L\u{e9}a \t DUPONT "));
+		assert_eq!("lea", loose.normalize("Le\u{301}a"));
+		assert_eq!(loose.normalize("Stra\u{df}e"), loose.normalize("STRASSE"));
+		assert_eq!(
+			loose.normalize("\u{3a3}\u{3bf}\u{3c2}"),
+			loose.normalize("\u{3c3}\u{3bf}\u{3c3}")
+		);
+		assert_ne!(exact.normalize("Bob"), exact.normalize("bob"));
+
+		let ballot_paper = BallotPaper::ranked(
+			Voter(" L\u{e9}a ".to_string()),
+			vec![
+				Candidate("MACRON".to_string()),
+				Candidate("M\u{e9}lenchon".to_string()),
+			],
+		)
+		.cast_by(Voter("MALO".to_string()))
+		.normalized(&loose);
+		assert_eq!(Voter("lea".to_string()), ballot_paper.voter);
+		assert_eq!(Some(Voter("malo".to_string())), ballot_paper.cast_by);
+		assert_eq!(
+			Choice::Ranked(vec![
+				Candidate("macron".to_string()),
+				Candidate("melenchon".to_string())
+			]),
+			ballot_paper.choice
+		);
+
+		let mut scoreboard = Scoreboard::new(vec![
+			Candidate("Macron".to_string()),
+			Candidate("macron ".to_string()),
+		]);
+		scoreboard
+			.scores
+			.insert(Candidate("Macron".to_string()), Score(2));
+		scoreboard
+			.scores
+			.insert(Candidate("macron ".to_string()), Score(1));
+		let mut voters = AttendenceSheet::default();
+		voters.0.insert(Voter("Malo".to_string()));
+		voters.0.insert(Voter("malo ".to_string()));
+		let machine = VotingMachine::new(voters, scoreboard)
+			.with_identity(loose)
+			.normalized();
+		assert_eq!(
+			vec![(&Candidate("macron".to_string()), &Score(3))],
+			machine.get_scoreboard().scores.iter().collect::<Vec<_>>()
+		);
+		assert_eq!(
+			vec![&Voter("malo".to_string())],
+			machine.get_voter().0.iter().collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn recount_from_tally() {
+		let (a, b, c) = (
+			Candidate("A".to_string()),
+			Candidate("B".to_string()),
+			Candidate("C".to_string()),
+		);
+		let mut machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]),
+		)
+		.with_mode(ElectionMode::InstantRunoff)
+		.with_secret(true);
+		for (voter, ranking) in [
+			("Malo", vec![a.clone(), b.clone()]),
+			("Lea", vec![a.clone()]),
+			("Ines", vec![b.clone(), c.clone()]),
+			("Tom", vec![c.clone(), a.clone()]),
+		] {
+			machine.vote(BallotPaper::ranked(Voter(voter.to_string()), ranking));
+		}
+		machine.withdraw_candidate(a.clone(), WithdrawalPolicy::Redistribute);
+		machine.rename_candidate(c, Candidate("D".to_string()));
+
+		let recount = machine.recount();
+		assert_eq!(4, recount.ballots);
+		assert!(recount.discrepancies.is_empty());
+
+		let mut tampered = machine.get_scoreboard().clone();
+		tampered.scores.insert(b, Score(3));
+		assert_eq!(
+			vec![Discrepancy {
+				line: TallyLine::Candidate(Candidate("B".to_string())),
+				stored: Score(3),
+				recounted: Score(2),
+			}],
+			machine.get_tally().recount(&tampered).discrepancies
+		);
+	}
+
+	#[test]
+	fn single_transferable_vote_exact_quota() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::SingleTransferableVote { seats: 2 });
+
+		let rankings = [
+			(3, vec![a.clone(), b.clone()]),
+			(1, vec![b.clone()]),
+			(2, vec![c.clone()]),
+		];
+		let mut number = 0;
+		for (count, ranking) in rankings {
+			for _ in 0..count {
+				number += 1;
+				voting_machine.vote(BallotPaper::ranked(
+					Voter(number.to_string()),
+					ranking.clone(),
+				));
+			}
+		}
+
+		let stv = voting_machine.single_transferable_vote();
+		assert_eq!(Votes(3000), stv.quota);
+		assert_eq!(vec![a.clone()], stv.rounds[0].elected);
+		assert!(stv.rounds[0].transfers.is_empty());
+		assert_eq!(Votes(1000), stv.rounds[1].tallies[&b]);
+		assert_eq!(vec![a, c], stv.elected);
+	}
+
+	#[test]
+	fn instant_runoff_tie_for_last() {
+		use super::instant_runoff::{LastPlaceRule, LastPlaceTie};
+
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::InstantRunoff);
+
+		let rankings = [
+			(4, vec![a.clone()]),
+			(3, vec![b.clone(), c.clone()]),
+			(3, vec![c.clone(), b.clone()]),
+		];
+		let mut number = 0;
+		for (count, ranking) in rankings {
+			for _ in 0..count {
+				number += 1;
+				voting_machine.vote(BallotPaper::ranked(
+					Voter(number.to_string()),
+					ranking.clone(),
+				));
+			}
+		}
+
+		let runoff = voting_machine.instant_runoff();
+		assert_eq!(2, runoff.rounds.len());
+		assert_eq!(Some(c.clone()), runoff.rounds[0].eliminated);
+		assert_eq!(
+			Some(LastPlaceTie {
+				tied: vec![b.clone(), c],
+				settled_by: LastPlaceRule::NameOrder,
+			}),
+			runoff.rounds[0].last_place_tie
+		);
+		assert_eq!(Score(6), runoff.rounds[1].scores[&b]);
+		assert_eq!(Some(b), runoff.winner);
+	}
+
+	#[test]
+	fn instant_runoff_tie_settled_by_earlier_round() {
+		use super::instant_runoff::LastPlaceRule;
+
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+		let d = Candidate("D".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone(), d.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::InstantRunoff);
+
+		let rankings = [
+			(5, vec![a.clone()]),
+			(4, vec![b.clone()]),
+			(3, vec![c.clone(), b.clone()]),
+			(1, vec![d.clone(), c.clone()]),
+		];
+		let mut number = 0;
+		for (count, ranking) in rankings {
+			for _ in 0..count {
+				number += 1;
+				voting_machine.vote(BallotPaper::ranked(
+					Voter(number.to_string()),
+					ranking.clone(),
+				));
+			}
+		}
+
+		let runoff = voting_machine.instant_runoff();
+		assert_eq!(Some(d), runoff.rounds[0].eliminated);
+		assert_eq!(Some(c.clone()), runoff.rounds[1].eliminated);
+		assert_eq!(
+			Some(LastPlaceRule::EarlierRound(1)),
+			runoff.rounds[1]
+				.last_place_tie
+				.as_ref()
+				.map(|tie| tie.settled_by)
+		);
+		assert_eq!(Some(b), runoff.winner);
+	}
+
+	#[test]
+	fn two_round_tie_for_second_place() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+		let first_round = |tie_break: TieBreakPolicy| {
+			let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+			let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+				.with_mode(ElectionMode::TwoRound)
+				.with_tie_break(tie_break);
+			for (number, candidate) in [&a, &a, &a, &b, &b, &c, &c].into_iter().enumerate() {
+				voting_machine.vote(BallotPaper::new(
+					Voter(number.to_string()),
+					Some(candidate.clone()),
+				));
+			}
+			voting_machine
+		};
+
+		let mut unresolved = first_round(TieBreakPolicy::Unresolved);
+		assert_eq!(
+			RoundOutcome::Tie(vec![b.clone(), c.clone()]),
+			unresolved.close_round()
+		);
+		assert_eq!(1, unresolved.get_round());
+
+		let mut runoff = first_round(TieBreakPolicy::Runoff);
+		assert_eq!(
+			RoundOutcome::Runoff(vec![a.clone(), b.clone(), c.clone()]),
+			runoff.close_round()
+		);
+		assert_eq!(2, runoff.get_round());
+		assert_eq!(
+			&[TieBreak {
+				tied: vec![b.clone(), c.clone()],
+				resolution: TieResolution::Runoff(vec![b.clone(), c.clone()]),
+			}],
+			runoff.get_tie_breaks()
+		);
+
+		let mut oldest = first_round(TieBreakPolicy::Oldest(BTreeMap::from([(
+			c.clone(),
+			BirthDate {
+				year: 1950,
+				month: 1,
+				day: 1,
+			},
+		)])));
+		assert_eq!(
+			RoundOutcome::SecondRound(a.clone(), c.clone()),
+			oldest.close_round()
+		);
+		assert_eq!(1, oldest.get_tie_breaks().len());
+
+		for (number, candidate) in [&a, &a, &c].into_iter().enumerate() {
+			oldest.vote(BallotPaper::new(
+				Voter(number.to_string()),
+				Some(candidate.clone()),
+			));
+		}
+		assert_eq!(RoundOutcome::Elected(a), oldest.close_round());
+		assert_eq!(ElectionState::Closed, oldest.get_state());
+	}
+
+	#[test]
+	fn single_transferable_vote_tie_for_last() {
+		use super::instant_runoff::{LastPlaceRule, LastPlaceTie};
+
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let scoreboard = Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]);
+		let mut voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::SingleTransferableVote { seats: 1 });
+
+		let rankings = [
+			(4, vec![a.clone()]),
+			(3, vec![b.clone(), c.clone()]),
+			(3, vec![c.clone(), b.clone()]),
+		];
+		let mut number = 0;
+		for (count, ranking) in rankings {
+			for _ in 0..count {
+				number += 1;
+				voting_machine.vote(BallotPaper::ranked(
+					Voter(number.to_string()),
+					ranking.clone(),
+				));
+			}
+		}
+
+		let stv = voting_machine.single_transferable_vote();
+		assert_eq!(vec![c.clone()], stv.rounds[0].eliminated);
+		assert_eq!(
+			Some(LastPlaceTie {
+				tied: vec![b.clone(), c],
+				settled_by: LastPlaceRule::NameOrder,
+			}),
+			stv.rounds[0].last_place_tie
+		);
+		assert_eq!(None, stv.rounds[1].last_place_tie);
+		assert_eq!(vec![b], stv.elected);
+	}
+
+	#[test]
+	fn kept_withdrawn_candidate_cannot_win() {
+		let a = Candidate("A".to_string());
+		let b = Candidate("B".to_string());
+		let c = Candidate("C".to_string());
+
+		let mut plurality = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]),
+		);
+		for (voter, candidate) in [("1", &a), ("2", &a), ("3", &a), ("4", &b), ("5", &c)] {
+			plurality.vote(BallotPaper::new(
+				Voter(voter.to_string()),
+				Some(candidate.clone()),
+			));
+		}
+		plurality.withdraw_candidate(a.clone(), WithdrawalPolicy::Keep);
+		let results = plurality.results();
+		assert_eq!(Score(3), results.ranking[0].score);
+		assert!(results.ranking[0].withdrawn);
+		assert_eq!(Majority::Tie(vec![b.clone(), c.clone()]), results.majority);
+
+		let mut runoff = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![a.clone(), b.clone(), c.clone()]),
+		)
+		.with_mode(ElectionMode::InstantRunoff);
+		let rankings = [
+			(3, vec![a.clone(), c.clone()]),
+			(2, vec![b.clone()]),
+			(1, vec![c.clone()]),
+		];
+		let mut number = 0;
+		for (count, ranking) in rankings {
+			for _ in 0..count {
+				number += 1;
+				runoff.vote(BallotPaper::ranked(
+					Voter(number.to_string()),
+					ranking.clone(),
+				));
+			}
+		}
+		assert_eq!(Some(a.clone()), runoff.instant_runoff().winner);
+		runoff.withdraw_candidate(a.clone(), WithdrawalPolicy::Keep);
+		assert_eq!(Some(c.clone()), runoff.instant_runoff().winner);
+		assert_eq!(Some(c.clone()), runoff.schulze().condorcet_winner);
+		assert_eq!(vec![c], runoff.single_transferable_vote().elected);
+	}
+
+	#[test]
+	fn ballot_box_forgets_insertion_order() {
+		use super::ballot_box::BallotBox;
+
+		let ballots: Vec<Vec<Candidate>> = (0..100)
+			.map(|number| vec![Candidate(number.to_string())])
+			.collect();
+		let mut ballot_box = BallotBox::default();
+		for ballot in ballots.clone() {
+			ballot_box.shuffle_in(ballot);
+		}
+		assert_ne!(ballots, ballot_box.0);
+		let mut sorted = ballot_box.0.clone();
+		sorted.sort();
+		let mut expected = ballots;
+		expected.sort();
+		assert_eq!(expected, sorted);
+	}
 }
@@ -0,0 +1,25 @@
+use std::collections::BTreeMap;
+
+use super::generic_domains::Voter;
+
+/// Proxies given for the election, from the voter who gave them to the voter who holds them.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Proxies(pub BTreeMap<Voter, Voter>);
+
+impl Proxies {
+	#[must_use]
+	pub fn held_by(&self, holder: &Voter) -> usize {
+		self.0.values().filter(|voter| *voter == holder).count()
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProxyOutcome {
+	Registered(Voter, Voter),
+	SameVoter(Voter),
+	NotRegistered(Voter),
+	HasAlreadyVoted(Voter),
+	AlreadyGiven(Voter),
+	TooManyProxies(Voter),
+	ElectionClosed,
+}
@@ -0,0 +1,72 @@
+use std::{
+	fmt::{Display, Write},
+	hash::{BuildHasher, RandomState},
+};
+
+use sha2::{Digest, Sha256};
+
+use super::{
+	ballot_paper::Choice,
+	generic_domains::{Candidate, Voter},
+	vote_outcome::VoteOutcome,
+};
+
+/// Hash commitment to a ballot, handed to its voter and published on the
+/// bulletin board.
+///
+/// The ballot is hashed along with a random nonce that is thrown away, so
+/// the code cannot be matched back to a choice by trying every candidate.
+#[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Debug)]
+pub struct Receipt(pub String);
+
+impl Display for Receipt {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl Receipt {
+	#[must_use]
+	pub fn commit(voter: &Voter, choice: &Choice) -> Self {
+		let state = RandomState::new();
+		let mut hasher = Sha256::new();
+		hasher.update(state.hash_one(0_u8).to_le_bytes());
+		hasher.update(state.hash_one(1_u8).to_le_bytes());
+		hash_name(&mut hasher, &voter.0);
+		hash_choice(&mut hasher, choice);
+		Self(to_hex(&hasher.finalize()))
+	}
+}
+
+pub(super) fn hash_choice(hasher: &mut Sha256, choice: &Choice) {
+	let (tag, candidates): (u8, Vec<&Candidate>) = match choice {
+		Choice::Single(candidate) => (0, candidate.iter().collect()),
+		Choice::Ranked(ranking) => (1, ranking.iter().collect()),
+		Choice::Approval(approved) => (2, approved.iter().collect()),
+	};
+	hasher.update([tag]);
+	hasher.update((candidates.len() as u64).to_le_bytes());
+	for candidate in candidates {
+		hash_name(hasher, &candidate.0);
+	}
+}
+
+pub(super) fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().fold(String::new(), |mut hex, byte| {
+		let _ = write!(hex, "{byte:02x}");
+		hex
+	})
+}
+
+/// Prefixes `name` with its length, so that no two lists of names hash alike.
+pub(super) fn hash_name(hasher: &mut Sha256, name: &str) {
+	hasher.update((name.len() as u64).to_le_bytes());
+	hasher.update(name.as_bytes());
+}
+
+/// A vote outcome, with the receipt of the ballot when it went into the count.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReceiptedVote {
+	pub outcome: VoteOutcome,
+	pub receipt: Option<Receipt>,
+}
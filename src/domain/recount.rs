@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use super::{
+	ballot_box::random_position,
+	counted_ballot::CountedBallot,
+	generic_domains::{Candidate, Score},
+	scoreboard::Scoreboard,
+};
+
+/// Every ballot of the round that went into the count, without its voter, so
+/// that the scoreboard can be tallied again from scratch.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Tally(pub Vec<CountedBallot>);
+
+impl Tally {
+	/// Slips `ballot` in at a random place, like `BallotBox::shuffle_in`.
+	pub fn shuffle_in(&mut self, ballot: CountedBallot) {
+		self.0.insert(random_position(self.0.len()), ballot);
+	}
+
+	/// Takes one ballot equal to `ballot` back out, for a voter who voted again.
+	pub fn take_back(&mut self, ballot: &CountedBallot) {
+		if let Some(position) = self.0.iter().position(|kept| kept == ballot) {
+			self.0.remove(position);
+		}
+	}
+
+	/// Counts the ballots again and compares the result with `stored`.
+	#[must_use]
+	pub fn recount(&self, stored: &Scoreboard) -> Recount {
+		let mut scores: BTreeMap<Candidate, Score> = stored
+			.scores
+			.keys()
+			.map(|candidate| (candidate.clone(), Score::default()))
+			.collect();
+		let (mut blank_score, mut invalid_score) = (Score::default(), Score::default());
+		for ballot in &self.0 {
+			let candidates = match ballot {
+				CountedBallot::Blank => {
+					blank_score.0 += 1;
+					continue;
+				}
+				CountedBallot::Invalid => {
+					invalid_score.0 += 1;
+					continue;
+				}
+				CountedBallot::Candidate(candidate) => std::slice::from_ref(candidate),
+				CountedBallot::Ranked(ranking) => &ranking[..ranking.len().min(1)],
+				CountedBallot::Approved(approved) => approved.as_slice(),
+			};
+			for candidate in candidates {
+				scores.entry(candidate.clone()).or_default().0 += 1;
+			}
+		}
+
+		let mut discrepancies = Vec::new();
+		let mut compare = |line: TallyLine, stored: Score, recounted: Score| {
+			if stored != recounted {
+				discrepancies.push(Discrepancy {
+					line,
+					stored,
+					recounted,
+				});
+			}
+		};
+		for (candidate, recounted) in scores {
+			let stored = stored.scores.get(&candidate).copied().unwrap_or_default();
+			compare(TallyLine::Candidate(candidate), stored, recounted);
+		}
+		compare(TallyLine::Blank, stored.blank_score, blank_score);
+		compare(TallyLine::Invalid, stored.invalid_score, invalid_score);
+		Recount {
+			ballots: self.0.len(),
+			discrepancies,
+		}
+	}
+}
+
+/// Line of the scoreboard.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum TallyLine {
+	Candidate(Candidate),
+	Blank,
+	Invalid,
+}
+
+/// A line whose stored score differs from the recounted one.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Discrepancy {
+	pub line: TallyLine,
+	pub stored: Score,
+	pub recounted: Score,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Recount {
+	/// Number of ballots counted again.
+	pub ballots: usize,
+	/// Empty when the stored scoreboard matches the recount.
+	pub discrepancies: Vec<Discrepancy>,
+}
+
+impl Recount {
+	/// Tells whether the scoreboard counts ballots none of which were kept.
+	#[must_use]
+	pub fn lacks_ballots(&self) -> bool {
+		self.ballots == 0 && !self.discrepancies.is_empty()
+	}
+}
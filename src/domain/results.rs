@@ -0,0 +1,126 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use super::{
+	generic_domains::{Candidate, Score},
+	scoreboard::Scoreboard,
+};
+
+/// Share of a total, counted in hundredths of a percent.
+#[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Copy, Default, Debug)]
+pub struct Percentage(pub u64);
+
+impl Percentage {
+	pub const HUNDRED: Self = Self(10_000);
+
+	/// Returns `part` out of `total`, rounded to the nearest hundredth.
+	#[must_use]
+	pub fn of(part: usize, total: usize) -> Self {
+		if total == 0 {
+			return Self::default();
+		}
+		let (part, total) = (part as u64, total as u64);
+		Self((part * Self::HUNDRED.0 * 2 + total) / (total * 2))
+	}
+}
+
+impl Display for Percentage {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}.{:02}%", self.0 / 100, self.0 % 100)
+	}
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct CandidateResult {
+	/// Position in the ranking, starting at 1 and shared by tied candidates.
+	pub rank: usize,
+	pub candidate: Candidate,
+	pub score: Score,
+	/// Share of the ballots cast for a candidate.
+	pub of_valid: Percentage,
+	/// Share of every ballot cast, blank and invalid ones included.
+	pub of_cast: Percentage,
+	/// The candidate was withdrawn: their votes still count but they cannot win.
+	pub withdrawn: bool,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Majority {
+	/// The leader holds more than half of the valid votes.
+	Absolute(Candidate),
+	/// The leader is ahead of everyone without reaching half of the valid votes.
+	Relative(Candidate),
+	/// Several candidates share the lead.
+	Tie(Vec<Candidate>),
+	NoValidVote,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Results {
+	pub ranking: Vec<CandidateResult>,
+	pub valid_votes: usize,
+	pub cast_votes: usize,
+	pub majority: Majority,
+}
+
+impl Results {
+	#[must_use]
+	pub fn from_scoreboard(scoreboard: &Scoreboard) -> Self {
+		let valid_votes = scoreboard.valid_votes();
+		let cast_votes = valid_votes + scoreboard.blank_score.0 + scoreboard.invalid_score.0;
+
+		let mut ranking: Vec<CandidateResult> = Vec::new();
+		for (position, (candidate, score)) in scoreboard.ranking().into_iter().enumerate() {
+			let rank = match ranking.last() {
+				Some(previous) if previous.score == score => previous.rank,
+				_ => position + 1,
+			};
+			ranking.push(CandidateResult {
+				rank,
+				candidate,
+				score,
+				of_valid: Percentage::of(score.0, valid_votes),
+				of_cast: Percentage::of(score.0, cast_votes),
+				withdrawn: false,
+			});
+		}
+
+		let majority = majority(&ranking, valid_votes);
+		Self {
+			ranking,
+			valid_votes,
+			cast_votes,
+			majority,
+		}
+	}
+
+	/// Marks the `withdrawn` candidates, who keep their place in the ranking,
+	/// and settles the majority among the candidates still standing.
+	#[must_use]
+	pub fn with_withdrawn(mut self, withdrawn: &BTreeSet<Candidate>) -> Self {
+		for result in &mut self.ranking {
+			result.withdrawn = withdrawn.contains(&result.candidate);
+		}
+		self.majority = majority(&self.ranking, self.valid_votes);
+		self
+	}
+}
+
+/// Finds the leaders of the `ranking` among the candidates still standing.
+fn majority(ranking: &[CandidateResult], valid_votes: usize) -> Majority {
+	let standing: Vec<&CandidateResult> =
+		ranking.iter().filter(|result| !result.withdrawn).collect();
+	let Some(first) = standing.first() else {
+		return Majority::NoValidVote;
+	};
+	let leaders: Vec<Candidate> = standing
+		.iter()
+		.filter(|result| result.score == first.score)
+		.map(|result| result.candidate.clone())
+		.collect();
+	match leaders.as_slice() {
+		_ if first.score.0 == 0 => Majority::NoValidVote,
+		[leader] if first.score.0 * 2 > valid_votes => Majority::Absolute(leader.clone()),
+		[leader] => Majority::Relative(leader.clone()),
+		_ => Majority::Tie(leaders),
+	}
+}
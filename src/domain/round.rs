@@ -0,0 +1,21 @@
+use super::{
+	generic_domains::{AttendenceSheet, Candidate},
+	scoreboard::Scoreboard,
+};
+
+/// A closed round of a two-round election, kept once the next round has started.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ArchivedRound {
+	pub voters: AttendenceSheet,
+	pub scoreboard: Scoreboard,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RoundOutcome {
+	Elected(Candidate),
+	SecondRound(Candidate, Candidate),
+	Tie(Vec<Candidate>),
+	/// The tie-break policy started a new round between these candidates.
+	Runoff(Vec<Candidate>),
+	NotTwoRound,
+}
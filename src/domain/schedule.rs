@@ -0,0 +1,67 @@
+use super::{
+	election_state::ElectionState, generic_domains::Voter, vote_log::Timestamp,
+	vote_outcome::VoteOutcome, voting_event::VotingEvent,
+};
+
+/// Window during which an election takes votes, unbounded on a side left unset.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct Schedule {
+	pub opens: Option<Timestamp>,
+	pub closes: Option<Timestamp>,
+}
+
+/// Where the clock stands in the window.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Countdown {
+	/// Seconds left before the election opens.
+	Opens(u64),
+	/// Seconds left before the election closes.
+	Closes(u64),
+	DeadlinePassed,
+	Unscheduled,
+}
+
+impl Schedule {
+	fn has_opened(&self, now: Timestamp) -> bool {
+		self.opens.is_none_or(|opens| opens <= now)
+	}
+
+	fn has_closed(&self, now: Timestamp) -> bool {
+		self.closes.is_some_and(|closes| closes <= now)
+	}
+
+	/// Refuses a ballot cast at `timestamp` outside of the window.
+	#[must_use]
+	pub fn refuse(&self, voter: &Voter, timestamp: Timestamp) -> Option<VoteOutcome> {
+		if self.has_closed(timestamp) {
+			Some(VoteOutcome::DeadlinePassed(voter.clone()))
+		} else if !self.has_opened(timestamp) {
+			Some(VoteOutcome::ElectionNotOpen(voter.clone()))
+		} else {
+			None
+		}
+	}
+
+	/// Returns the transitions an election in `state` is owed at `now`, in order.
+	#[must_use]
+	pub fn due(&self, state: ElectionState, now: Timestamp) -> Vec<VotingEvent> {
+		let mut events = Vec::new();
+		if state == ElectionState::NotOpen && self.opens.is_some() && self.has_opened(now) {
+			events.push(VotingEvent::ElectionOpened);
+		}
+		if state != ElectionState::Closed && self.has_closed(now) {
+			events.push(VotingEvent::ElectionClosed);
+		}
+		events
+	}
+
+	#[must_use]
+	pub fn countdown(&self, now: Timestamp) -> Countdown {
+		match (self.opens, self.closes) {
+			(Some(opens), _) if now < opens => Countdown::Opens(opens.0 - now.0),
+			(_, Some(closes)) if now < closes => Countdown::Closes(closes.0 - now.0),
+			(_, Some(_)) => Countdown::DeadlinePassed,
+			(_, None) => Countdown::Unscheduled,
+		}
+	}
+}
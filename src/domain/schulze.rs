@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use super::{
+	ballot_box::BallotBox,
+	generic_domains::{Candidate, Score},
+};
+
+pub type Matrix = BTreeMap<Candidate, BTreeMap<Candidate, Score>>;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Schulze {
+	/// Number of ballots preferring the row candidate over the column candidate.
+	pub pairwise: Matrix,
+	pub strongest_paths: Matrix,
+	pub condorcet_winner: Option<Candidate>,
+	/// Candidates from best to worst, tied candidates sharing the same rank.
+	pub ranking: Vec<Vec<Candidate>>,
+}
+
+impl Schulze {
+	/// Builds the pairwise preference matrix of the ranked ballots, then the
+	/// strongest paths between every pair of candidates. Candidates left out
+	/// of a ballot are ranked below every candidate that appears on it.
+	#[must_use]
+	pub fn tally<'a>(
+		candidates: impl IntoIterator<Item = &'a Candidate>,
+		ballots: &BallotBox,
+	) -> Self {
+		let candidates: Vec<Candidate> = candidates.into_iter().cloned().collect();
+		let mut pairwise: Matrix = candidates
+			.iter()
+			.map(|row| {
+				let columns = candidates
+					.iter()
+					.filter(|column| *column != row)
+					.map(|column| (column.clone(), Score::default()))
+					.collect();
+				(row.clone(), columns)
+			})
+			.collect();
+
+		for ballot in &ballots.0 {
+			let rank = |candidate: &Candidate| {
+				ballot
+					.iter()
+					.position(|ranked| ranked == candidate)
+					.unwrap_or(ballot.len())
+			};
+			for (row, columns) in &mut pairwise {
+				for (column, score) in columns.iter_mut() {
+					if rank(row) < rank(column) {
+						score.0 += 1;
+					}
+				}
+			}
+		}
+
+		let beats = |matrix: &Matrix, a: &Candidate, b: &Candidate| matrix[a][b] > matrix[b][a];
+
+		let condorcet_winner = candidates
+			.iter()
+			.find(|a| {
+				candidates
+					.iter()
+					.filter(|b| b != a)
+					.all(|b| beats(&pairwise, a, b))
+			})
+			.cloned();
+
+		let mut strongest_paths = pairwise.clone();
+		for (row, columns) in &mut strongest_paths {
+			for (column, score) in columns.iter_mut() {
+				if pairwise[row][column] <= pairwise[column][row] {
+					*score = Score::default();
+				}
+			}
+		}
+		for i in &candidates {
+			for j in candidates.iter().filter(|j| *j != i) {
+				for k in candidates.iter().filter(|k| *k != i && *k != j) {
+					let through = strongest_paths[j][i].min(strongest_paths[i][k]);
+					if let Some(score) = strongest_paths.get_mut(j).and_then(|row| row.get_mut(k)) {
+						*score = (*score).max(through);
+					}
+				}
+			}
+		}
+
+		let mut ranking: Vec<Vec<Candidate>> = Vec::new();
+		let mut wins: Vec<(usize, &Candidate)> = candidates
+			.iter()
+			.map(|a| {
+				let count = candidates
+					.iter()
+					.filter(|b| *b != a && beats(&strongest_paths, a, b))
+					.count();
+				(count, a)
+			})
+			.collect();
+		wins.sort_by(|left, right| right.0.cmp(&left.0).then(left.1.cmp(right.1)));
+		let mut previous = None;
+		for (count, candidate) in wins {
+			match ranking.last_mut() {
+				Some(tier) if previous == Some(count) => tier.push(candidate.clone()),
+				_ => ranking.push(vec![candidate.clone()]),
+			}
+			previous = Some(count);
+		}
+
+		Self {
+			pairwise,
+			strongest_paths,
+			condorcet_winner,
+			ranking,
+		}
+	}
+}
@@ -1,12 +1,21 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use super::generic_domains::{Candidate, Score};
+use super::{
+	ballot_box::BallotBox,
+	candidate_profile::CandidateProfile,
+	generic_domains::{Candidate, Score},
+	instant_runoff::InstantRunoff,
+	results::Results,
+	schulze::Schulze,
+	single_transferable_vote::SingleTransferableVote,
+};
 
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Scoreboard {
 	pub scores: BTreeMap<Candidate, Score>,
 	pub blank_score: Score,
 	pub invalid_score: Score,
+	pub profiles: BTreeMap<Candidate, CandidateProfile>,
 }
 
 impl Scoreboard {
@@ -21,6 +30,87 @@ impl Scoreboard {
 			scores,
 			blank_score: Score(0),
 			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
 		}
 	}
+
+	#[must_use]
+	pub fn with_profiles(mut self, profiles: BTreeMap<Candidate, CandidateProfile>) -> Self {
+		self.profiles = profiles;
+		self
+	}
+
+	/// Returns the name shown for `candidate`, its id when it has no profile.
+	#[must_use]
+	pub fn display_name<'a>(&'a self, candidate: &'a Candidate) -> &'a str {
+		self.profiles
+			.get(candidate)
+			.map_or(candidate.0.as_str(), |profile| {
+				profile.display_name.as_str()
+			})
+	}
+
+	/// Returns the candidates from most to least voted.
+	#[must_use]
+	pub fn ranking(&self) -> Vec<(Candidate, Score)> {
+		let mut ranking: Vec<(Candidate, Score)> = self
+			.scores
+			.iter()
+			.map(|(candidate, score)| (candidate.clone(), *score))
+			.collect();
+		ranking.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+		ranking
+	}
+
+	/// Returns the number of votes cast for a candidate, blank and invalid ballots excluded.
+	#[must_use]
+	pub fn valid_votes(&self) -> usize {
+		self.scores.values().map(|score| score.0).sum()
+	}
+
+	/// Computes the shares, ranking and majority of the candidates.
+	#[must_use]
+	pub fn results(&self) -> Results {
+		Results::from_scoreboard(self)
+	}
+
+	/// Returns the candidates of this scoreboard but the `withdrawn` ones.
+	fn standing<'a>(
+		&'a self,
+		withdrawn: &'a BTreeSet<Candidate>,
+	) -> impl Iterator<Item = &'a Candidate> {
+		self.scores
+			.keys()
+			.filter(|candidate| !withdrawn.contains(*candidate))
+	}
+
+	/// Replays the ranked ballots over the candidates of this scoreboard still
+	/// standing, skipping the `withdrawn` ones.
+	#[must_use]
+	pub fn instant_runoff(
+		&self,
+		ballots: &BallotBox,
+		withdrawn: &BTreeSet<Candidate>,
+	) -> InstantRunoff {
+		InstantRunoff::tally(self.standing(withdrawn), ballots)
+	}
+
+	/// Compares the candidates of this scoreboard still standing pairwise over
+	/// the ranked ballots.
+	#[must_use]
+	pub fn schulze(&self, ballots: &BallotBox, withdrawn: &BTreeSet<Candidate>) -> Schulze {
+		Schulze::tally(self.standing(withdrawn), ballots)
+	}
+
+	/// Fills `seats` seats among the candidates of this scoreboard still
+	/// standing from the ranked ballots.
+	#[must_use]
+	pub fn single_transferable_vote(
+		&self,
+		ballots: &BallotBox,
+		seats: usize,
+		withdrawn: &BTreeSet<Candidate>,
+	) -> SingleTransferableVote {
+		SingleTransferableVote::tally(self.standing(withdrawn), ballots, seats)
+	}
 }
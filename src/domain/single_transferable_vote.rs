@@ -0,0 +1,162 @@
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt::Display,
+};
+
+use super::{
+	ballot_box::BallotBox,
+	generic_domains::Candidate,
+	instant_runoff::{eliminate, LastPlaceTie},
+};
+
+/// Vote values in thousandths, so that surpluses can be transferred fractionally.
+#[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Copy, Default, Debug)]
+pub struct Votes(pub u64);
+
+impl Votes {
+	pub const ONE: Self = Self(1000);
+}
+
+impl Display for Votes {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}.{:03}", self.0 / Self::ONE.0, self.0 % Self::ONE.0)
+	}
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Transfer {
+	pub from: Candidate,
+	pub votes: Votes,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct StvRound {
+	pub tallies: BTreeMap<Candidate, Votes>,
+	pub exhausted: Votes,
+	pub elected: Vec<Candidate>,
+	pub eliminated: Vec<Candidate>,
+	pub transfers: Vec<Transfer>,
+	/// How the candidate to eliminate was picked among those tied for last.
+	pub last_place_tie: Option<LastPlaceTie>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SingleTransferableVote {
+	pub seats: usize,
+	pub quota: Votes,
+	pub rounds: Vec<StvRound>,
+	pub elected: Vec<Candidate>,
+}
+
+impl SingleTransferableVote {
+	/// Fills the seats with the Droop quota. Candidates reaching the quota are
+	/// elected and their surplus is transferred at a reduced value; otherwise
+	/// the last candidate is eliminated and their ballots move on at full value.
+	/// A tie for last place is settled as in the instant runoff.
+	#[must_use]
+	pub fn tally<'a>(
+		candidates: impl IntoIterator<Item = &'a Candidate>,
+		ballots: &BallotBox,
+		seats: usize,
+	) -> Self {
+		let seats = seats.max(1);
+		let mut continuing: BTreeSet<Candidate> = candidates.into_iter().cloned().collect();
+		let mut weights: Vec<Votes> = vec![Votes::ONE; ballots.0.len()];
+		let quota_votes = u64::try_from(ballots.0.len() / (seats + 1) + 1).unwrap_or(u64::MAX);
+		let quota = Votes(quota_votes.saturating_mul(Votes::ONE.0));
+		let mut elected = Vec::new();
+		let mut rounds = Vec::new();
+
+		while elected.len() < seats && !continuing.is_empty() {
+			let holders: Vec<Option<&Candidate>> = ballots
+				.0
+				.iter()
+				.map(|ballot| {
+					ballot
+						.iter()
+						.find(|candidate| continuing.contains(*candidate))
+				})
+				.collect();
+			let mut tallies: BTreeMap<Candidate, Votes> = continuing
+				.iter()
+				.map(|candidate| (candidate.clone(), Votes::default()))
+				.collect();
+			let mut exhausted = Votes::default();
+			for (holder, weight) in holders.iter().zip(&weights) {
+				match holder.and_then(|candidate| tallies.get_mut(candidate)) {
+					Some(votes) => votes.0 += weight.0,
+					None => exhausted.0 += weight.0,
+				}
+			}
+
+			let mut round = StvRound {
+				tallies: tallies.clone(),
+				exhausted,
+				elected: Vec::new(),
+				eliminated: Vec::new(),
+				transfers: Vec::new(),
+				last_place_tie: None,
+			};
+			let remaining = seats - elected.len();
+			let mut reached: Vec<(Candidate, Votes)> = tallies
+				.into_iter()
+				.filter(|(_, votes)| *votes >= quota)
+				.collect();
+			reached.sort_by_key(|(_, votes)| std::cmp::Reverse(*votes));
+			reached.truncate(remaining);
+
+			if !reached.is_empty() {
+				for (candidate, votes) in reached {
+					// The ballots of the elected candidate keep only the surplus between
+					// them, nothing at all when the quota was reached exactly.
+					let surplus = votes.0 - quota.0;
+					for (holder, weight) in holders.iter().zip(weights.iter_mut()) {
+						if *holder == Some(&candidate) {
+							weight.0 = weight.0 * surplus / votes.0;
+						}
+					}
+					if surplus > 0 {
+						round.transfers.push(Transfer {
+							from: candidate.clone(),
+							votes: Votes(surplus),
+						});
+					}
+					round.elected.push(candidate);
+				}
+			} else if continuing.len() <= remaining {
+				round.elected.extend(continuing.iter().cloned());
+			} else {
+				let lowest = round.tallies.values().min().copied().unwrap_or_default();
+				let last: Vec<Candidate> = round
+					.tallies
+					.iter()
+					.filter(|(_, votes)| **votes == lowest)
+					.map(|(candidate, _)| candidate.clone())
+					.collect();
+				if let Some((candidate, last_place_tie)) =
+					eliminate(last, rounds.iter().map(|round: &StvRound| &round.tallies))
+				{
+					round.transfers.push(Transfer {
+						from: candidate.clone(),
+						votes: lowest,
+					});
+					round.eliminated.push(candidate);
+					round.last_place_tie = last_place_tie;
+				}
+			}
+
+			for candidate in round.elected.iter().chain(&round.eliminated) {
+				continuing.remove(candidate);
+			}
+			elected.extend(round.elected.iter().cloned());
+			rounds.push(round);
+		}
+
+		Self {
+			seats,
+			quota,
+			rounds,
+			elected,
+		}
+	}
+}
@@ -0,0 +1,112 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use super::generic_domains::Candidate;
+
+#[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Copy, Debug)]
+pub struct BirthDate {
+	pub year: u16,
+	pub month: u8,
+	pub day: u8,
+}
+
+impl Display for BirthDate {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+	}
+}
+
+#[derive(Error, Debug)]
+#[error("expected a YYYY-MM-DD birth date, got {0}")]
+pub struct InvalidBirthDate(String);
+
+impl FromStr for BirthDate {
+	type Err = InvalidBirthDate;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		let invalid = || InvalidBirthDate(value.to_string());
+		let mut parts = value.splitn(3, '-');
+		let mut next = || parts.next().ok_or_else(invalid);
+		let (year, month, day) = (next()?, next()?, next()?);
+		let date = Self {
+			year: year.parse().map_err(|_| invalid())?,
+			month: month.parse().map_err(|_| invalid())?,
+			day: day.parse().map_err(|_| invalid())?,
+		};
+		if !(1..=12).contains(&date.month) || !(1..=31).contains(&date.day) {
+			return Err(invalid());
+		}
+		Ok(date)
+	}
+}
+
+/// How a lead shared by several candidates is settled.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub enum TieBreakPolicy {
+	/// The tie is reported as is.
+	#[default]
+	Unresolved,
+	/// The oldest candidate wins; candidates without a known birth date come last.
+	Oldest(BTreeMap<Candidate, BirthDate>),
+	/// A draw that always picks the same candidate for the same seed and tie.
+	Draw(u64),
+	/// A new round is held between the tied candidates.
+	Runoff,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum TieResolution {
+	Winner(Candidate),
+	Runoff(Vec<Candidate>),
+	Unresolved,
+}
+
+/// A tie together with the way it was settled.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct TieBreak {
+	pub tied: Vec<Candidate>,
+	pub resolution: TieResolution,
+}
+
+impl TieBreakPolicy {
+	#[must_use]
+	pub fn resolve(&self, mut tied: Vec<Candidate>) -> TieBreak {
+		tied.sort();
+		let resolution = match self {
+			Self::Unresolved => TieResolution::Unresolved,
+			Self::Oldest(birth_dates) => {
+				let mut dated: Vec<(BirthDate, &Candidate)> = tied
+					.iter()
+					.filter_map(|candidate| Some((*birth_dates.get(candidate)?, candidate)))
+					.collect();
+				dated.sort();
+				match dated.as_slice() {
+					[(oldest, candidate), (next, _), ..] if oldest < next => {
+						TieResolution::Winner((*candidate).clone())
+					}
+					[(_, candidate)] => TieResolution::Winner((*candidate).clone()),
+					_ => TieResolution::Unresolved,
+				}
+			}
+			Self::Draw(seed) => {
+				let draw = split_mix(*seed);
+				let index = usize::try_from(draw % tied.len().max(1) as u64).unwrap_or_default();
+				tied.get(index)
+					.cloned()
+					.map_or(TieResolution::Unresolved, TieResolution::Winner)
+			}
+			Self::Runoff => TieResolution::Runoff(tied.clone()),
+		};
+		TieBreak { tied, resolution }
+	}
+}
+
+/// `SplitMix64`, kept here rather than borrowed from a crate so that a seed
+/// keeps drawing the same candidate across releases.
+const fn split_mix(seed: u64) -> u64 {
+	let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+	z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+	z ^ (z >> 31)
+}
@@ -0,0 +1,229 @@
+use std::{fmt::Display, net::SocketAddr, str::FromStr};
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::{
+	generic_domains::{Candidate, Voter},
+	receipt::{hash_name, to_hex},
+	tie_break::BirthDate,
+	vote_outcome::VoteOutcome,
+};
+
+/// Where a vote came in from.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum VoteSource {
+	Stdio,
+	Tcp(SocketAddr),
+	Udp(SocketAddr),
+	Web,
+}
+
+impl Display for VoteSource {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Stdio => write!(f, "stdio"),
+			Self::Tcp(peer) => write!(f, "tcp {peer}"),
+			Self::Udp(peer) => write!(f, "udp {peer}"),
+			Self::Web => write!(f, "web"),
+		}
+	}
+}
+
+/// Seconds since the Unix epoch.
+#[derive(Ord, PartialEq, PartialOrd, Eq, Clone, Copy, Default, Debug)]
+pub struct Timestamp(pub u64);
+
+impl Display for Timestamp {
+	/// Writes the UTC date and time, with the civil calendar conversion of
+	/// Howard Hinnant's `civil_from_days`.
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let (days, seconds) = (self.0 / 86_400, self.0 % 86_400);
+		let shifted = days + 719_468;
+		let era = shifted / 146_097;
+		let day_of_era = shifted % 146_097;
+		let year_of_era =
+			(day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+		let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+		let shifted_month = (5 * day_of_year + 2) / 153;
+		let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+		let month = if shifted_month < 10 {
+			shifted_month + 3
+		} else {
+			shifted_month - 9
+		};
+		let year = year_of_era + era * 400 + u64::from(month <= 2);
+		write!(
+			f,
+			"{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+			seconds / 3_600,
+			seconds % 3_600 / 60,
+			seconds % 60
+		)
+	}
+}
+
+#[derive(Error, Debug)]
+#[error("expected a YYYY-MM-DDTHH:MM:SS UTC date, got {0}")]
+pub struct InvalidTimestamp(String);
+
+impl FromStr for Timestamp {
+	type Err = InvalidTimestamp;
+
+	/// Reads a UTC date and time, with the calendar conversion of Howard
+	/// Hinnant's `days_from_civil`.
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		let invalid = || InvalidTimestamp(value.to_string());
+		let (date, time) = value
+			.trim_end_matches('Z')
+			.split_once(['T', ' '])
+			.ok_or_else(invalid)?;
+		// A calendar date reads the same as a birth date.
+		let date: BirthDate = date.parse().map_err(|_| invalid())?;
+		let mut parts = time.splitn(3, ':');
+		let mut next = || -> Result<u64, InvalidTimestamp> {
+			parts
+				.next()
+				.ok_or_else(invalid)?
+				.parse()
+				.map_err(|_| invalid())
+		};
+		let (hours, minutes, seconds) = (next()?, next()?, next()?);
+		if date.year < 1970 || hours > 23 || minutes > 59 || seconds > 59 {
+			return Err(invalid());
+		}
+		let month = u64::from(date.month);
+		let year = u64::from(date.year) - u64::from(month <= 2);
+		let (era, year_of_era) = (year / 400, year % 400);
+		let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+		let day_of_year = (153 * shifted_month + 2) / 5 + u64::from(date.day) - 1;
+		let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+		let days = era * 146_097 + day_of_era - 719_468;
+		Ok(Self(days * 86_400 + hours * 3_600 + minutes * 60 + seconds))
+	}
+}
+
+/// One vote attempt, chained to the entry before it by `previous`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct VoteLogEntry {
+	pub timestamp: Timestamp,
+	pub source: VoteSource,
+	pub outcome: VoteOutcome,
+	/// Hash of the previous entry, `GENESIS` for the first one.
+	pub previous: String,
+	/// Hash of this entry, `previous` included.
+	pub hash: String,
+}
+
+impl VoteLogEntry {
+	/// Hashes the content of the entry, whatever its stored `hash` says.
+	#[must_use]
+	pub fn digest(&self) -> String {
+		let mut hasher = Sha256::new();
+		hash_name(&mut hasher, &self.previous);
+		hasher.update(self.timestamp.0.to_le_bytes());
+		hash_name(&mut hasher, &self.source.to_string());
+		hash_outcome(&mut hasher, &self.outcome);
+		to_hex(&hasher.finalize())
+	}
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum ChainVerification {
+	/// Every link holds, over that many entries.
+	Intact(usize),
+	/// The entry at that position, starting at 1, does not match its hash
+	/// or the hash of the entry before it.
+	Broken(usize),
+}
+
+/// Append-only log of every vote attempt, refused ones included.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct VoteLog(pub Vec<VoteLogEntry>);
+
+impl VoteLog {
+	/// Hash the first entry is chained to.
+	pub const GENESIS: &'static str =
+		"0000000000000000000000000000000000000000000000000000000000000000";
+
+	pub fn append(&mut self, timestamp: Timestamp, source: VoteSource, outcome: VoteOutcome) {
+		let previous = self
+			.0
+			.last()
+			.map_or_else(|| Self::GENESIS.to_string(), |entry| entry.hash.clone());
+		let mut entry = VoteLogEntry {
+			timestamp,
+			source,
+			outcome,
+			previous,
+			hash: String::new(),
+		};
+		entry.hash = entry.digest();
+		self.0.push(entry);
+	}
+
+	/// Walks the chain from the first entry and stops at the first broken link.
+	#[must_use]
+	pub fn verify(&self) -> ChainVerification {
+		verify_chain(
+			self.0
+				.iter()
+				.map(|entry| (entry.previous.as_str(), entry.hash.as_str(), entry.digest())),
+		)
+	}
+}
+
+/// Walks `links`, each the previous hash, the stored hash and the digest of
+/// an entry, from the first one and stops at the first broken link.
+pub(super) fn verify_chain<'a>(
+	links: impl IntoIterator<Item = (&'a str, &'a str, String)>,
+) -> ChainVerification {
+	let mut expected = VoteLog::GENESIS;
+	let mut length = 0;
+	for (previous, hash, digest) in links {
+		length += 1;
+		if previous != expected || hash != digest {
+			return ChainVerification::Broken(length);
+		}
+		expected = hash;
+	}
+	ChainVerification::Intact(length)
+}
+
+fn hash_outcome(hasher: &mut Sha256, outcome: &VoteOutcome) {
+	let (tag, voters, candidates): (u8, Vec<&Voter>, Vec<&Candidate>) = match outcome {
+		VoteOutcome::AcceptedVote(voter, candidate) => (0, vec![voter], vec![candidate]),
+		VoteOutcome::AcceptedRankedVote(voter, ranking) => {
+			(1, vec![voter], ranking.iter().collect())
+		}
+		VoteOutcome::AcceptedApprovalVote(voter, approved, unknown) => {
+			hasher.update([2]);
+			hash_name(hasher, &voter.0);
+			hasher.update((approved.len() as u64).to_le_bytes());
+			for candidate in approved.iter().chain(unknown) {
+				hash_name(hasher, &candidate.0);
+			}
+			return;
+		}
+		VoteOutcome::BlankVote(voter) => (3, vec![voter], Vec::new()),
+		VoteOutcome::InvalidVote(voter) => (4, vec![voter], Vec::new()),
+		VoteOutcome::VoteChanged(outcome) => {
+			hasher.update([5]);
+			return hash_outcome(hasher, outcome);
+		}
+		VoteOutcome::BallotCast(voter) => (6, vec![voter], Vec::new()),
+		VoteOutcome::HasAlreadyVoted(voter) => (7, vec![voter], Vec::new()),
+		VoteOutcome::NotRegistered(voter) => (8, vec![voter], Vec::new()),
+		VoteOutcome::NotProxyHolder(holder, voter) => (9, vec![holder, voter], Vec::new()),
+		VoteOutcome::ElectionNotOpen(voter) => (10, vec![voter], Vec::new()),
+		VoteOutcome::ElectionClosed(voter) => (11, vec![voter], Vec::new()),
+		VoteOutcome::DeadlinePassed(voter) => (12, vec![voter], Vec::new()),
+	};
+	hasher.update([tag]);
+	for voter in voters {
+		hash_name(hasher, &voter.0);
+	}
+	for candidate in candidates {
+		hash_name(hasher, &candidate.0);
+	}
+}
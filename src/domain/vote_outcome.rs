@@ -1,9 +1,23 @@
 use super::generic_domains::{Candidate, Voter};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum VoteOutcome {
 	AcceptedVote(Voter, Candidate),
+	AcceptedRankedVote(Voter, Vec<Candidate>),
+	/// Approved candidates, then the unknown names that were left out of the count.
+	AcceptedApprovalVote(Voter, Vec<Candidate>, Vec<Candidate>),
 	BlankVote(Voter),
 	InvalidVote(Voter),
+	/// The ballot replaced an earlier one of the same voter, then counted as the inner outcome.
+	VoteChanged(Box<VoteOutcome>),
+	/// The ballot was counted in a secret election, which never tells what it held.
+	BallotCast(Voter),
 	HasAlreadyVoted(Voter),
+	NotRegistered(Voter),
+	/// The first voter holds no proxy from the second one.
+	NotProxyHolder(Voter, Voter),
+	ElectionNotOpen(Voter),
+	ElectionClosed(Voter),
+	/// The ballot came in after the scheduled closing time.
+	DeadlinePassed(Voter),
 }
@@ -0,0 +1,238 @@
+use sha2::{Digest, Sha256};
+
+use super::{
+	ballot_paper::BallotPaper,
+	candidate_change::{CandidateOutcome, WithdrawalPolicy},
+	election_state::TransitionOutcome,
+	generic_domains::{Candidate, QuestionId, Voter},
+	proxy::ProxyOutcome,
+	receipt::{hash_choice, hash_name, to_hex, Receipt, ReceiptedVote},
+	round::RoundOutcome,
+	vote_log::{verify_chain, ChainVerification, Timestamp, VoteLog, VoteSource},
+	voting_session::VotingSession,
+};
+
+/// Something that happened to a question. Replaying the events of a question
+/// in order rebuilds its machine.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum VotingEvent {
+	/// A ballot came in, counted or refused, along with the receipt it was given.
+	BallotCast {
+		ballot_paper: BallotPaper,
+		receipt: Receipt,
+		timestamp: Timestamp,
+		source: VoteSource,
+	},
+	/// A ballot of a secret question came in. Only its voter is kept: the
+	/// choice goes into the shuffled tally, and replaying the event checks the
+	/// voter in without counting anything.
+	CheckedIn {
+		voter: Voter,
+		cast_by: Option<Voter>,
+		receipt: Receipt,
+		timestamp: Timestamp,
+		source: VoteSource,
+	},
+	ProxyRegistered(Voter, Voter),
+	ElectionOpened,
+	ElectionClosed,
+	RoundClosed,
+	CandidateAdded(Candidate),
+	CandidateWithdrawn(Candidate, WithdrawalPolicy),
+	CandidateRenamed(Candidate, Candidate),
+}
+
+impl VotingEvent {
+	/// Returns the event as the history of a secret question keeps it, with
+	/// ballots down to the check-in of their voter.
+	#[must_use]
+	pub fn without_choice(self) -> Self {
+		match self {
+			Self::BallotCast {
+				ballot_paper,
+				receipt,
+				timestamp,
+				source,
+			} => Self::CheckedIn {
+				voter: ballot_paper.voter,
+				cast_by: ballot_paper.cast_by,
+				receipt,
+				timestamp,
+				source,
+			},
+			event => event,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EventOutcome {
+	Vote(ReceiptedVote),
+	Proxy(ProxyOutcome),
+	Transition(TransitionOutcome),
+	Round(RoundOutcome),
+	Candidate(CandidateOutcome),
+}
+
+/// An event, with the question it happened to.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct RecordedEvent {
+	pub question: QuestionId,
+	pub event: VotingEvent,
+}
+
+/// An event as the store keeps it, chained to the one before it, so that an
+/// event edited once stored no longer matches the hash stored with it.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ChainedEvent {
+	pub recorded: RecordedEvent,
+	/// Hash of the previous event, `VoteLog::GENESIS` for the first one.
+	pub previous: String,
+	/// Hash of this event, `previous` included, as stored when it was appended.
+	pub hash: String,
+}
+
+impl ChainedEvent {
+	/// Chains `recorded` after the last of `events`.
+	#[must_use]
+	pub fn following(events: &[Self], recorded: RecordedEvent) -> Self {
+		let previous = events
+			.last()
+			.map_or_else(|| VoteLog::GENESIS.to_string(), |event| event.hash.clone());
+		let mut chained = Self {
+			recorded,
+			previous,
+			hash: String::new(),
+		};
+		chained.hash = chained.digest();
+		chained
+	}
+
+	/// Hashes the content of the event, whatever its stored `hash` says.
+	#[must_use]
+	pub fn digest(&self) -> String {
+		let mut hasher = Sha256::new();
+		hash_name(&mut hasher, &self.previous);
+		hash_name(&mut hasher, &self.recorded.question.0);
+		hash_event(&mut hasher, &self.recorded.event);
+		to_hex(&hasher.finalize())
+	}
+
+	/// Checks the hash stored with each event against the event, from the
+	/// first one, and stops at the first broken link.
+	#[must_use]
+	pub fn verify(events: &[Self]) -> ChainVerification {
+		verify_chain(
+			events
+				.iter()
+				.map(|event| (event.previous.as_str(), event.hash.as_str(), event.digest())),
+		)
+	}
+}
+
+fn hash_event(hasher: &mut Sha256, event: &VotingEvent) {
+	let (tag, names): (u8, Vec<&str>) = match event {
+		VotingEvent::BallotCast {
+			ballot_paper,
+			receipt,
+			timestamp,
+			source,
+		} => {
+			hasher.update([0]);
+			hash_name(hasher, &ballot_paper.voter.0);
+			hash_choice(hasher, &ballot_paper.choice);
+			hash_ballot(
+				hasher,
+				ballot_paper.cast_by.as_ref(),
+				receipt,
+				*timestamp,
+				*source,
+			);
+			return;
+		}
+		VotingEvent::CheckedIn {
+			voter,
+			cast_by,
+			receipt,
+			timestamp,
+			source,
+		} => {
+			hasher.update([8]);
+			hash_name(hasher, &voter.0);
+			hash_ballot(hasher, cast_by.as_ref(), receipt, *timestamp, *source);
+			return;
+		}
+		VotingEvent::ProxyRegistered(voter, holder) => (1, vec![&voter.0, &holder.0]),
+		VotingEvent::ElectionOpened => (2, Vec::new()),
+		VotingEvent::ElectionClosed => (3, Vec::new()),
+		VotingEvent::RoundClosed => (4, Vec::new()),
+		VotingEvent::CandidateAdded(candidate) => (5, vec![&candidate.0]),
+		VotingEvent::CandidateWithdrawn(candidate, policy) => {
+			let policy = match policy {
+				WithdrawalPolicy::Keep => 0,
+				WithdrawalPolicy::Invalidate => 1,
+				WithdrawalPolicy::Redistribute => 2,
+			};
+			hasher.update([6, policy]);
+			hash_name(hasher, &candidate.0);
+			return;
+		}
+		VotingEvent::CandidateRenamed(candidate, new_name) => (7, vec![&candidate.0, &new_name.0]),
+	};
+	hasher.update([tag]);
+	for name in names {
+		hash_name(hasher, name);
+	}
+}
+
+fn hash_ballot(
+	hasher: &mut Sha256,
+	cast_by: Option<&Voter>,
+	receipt: &Receipt,
+	timestamp: Timestamp,
+	source: VoteSource,
+) {
+	match cast_by {
+		Some(holder) => {
+			hasher.update([1]);
+			hash_name(hasher, &holder.0);
+		}
+		None => hasher.update([0]),
+	}
+	hash_name(hasher, &receipt.0);
+	hasher.update(timestamp.0.to_le_bytes());
+	hash_name(hasher, &source.to_string());
+}
+
+/// The session as it stood once the first `events` events were applied.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Snapshot {
+	pub session: VotingSession,
+	pub events: usize,
+}
+
+impl Snapshot {
+	/// Returns the latest of `current` and `snapshots` taken after at most
+	/// `events` events.
+	#[must_use]
+	pub fn latest<'a>(
+		snapshots: &'a [Self],
+		current: Option<&'a Self>,
+		events: usize,
+	) -> Option<&'a Self> {
+		current
+			.into_iter()
+			.chain(snapshots.iter().rev())
+			.find(|snapshot| snapshot.events <= events)
+	}
+
+	/// Applies `events`, which follow the ones the snapshot already holds.
+	#[must_use]
+	pub fn replay(mut self, events: &[RecordedEvent]) -> Self {
+		for recorded in events {
+			self.session.apply(recorded.clone());
+		}
+		self.events += events.len();
+		self
+	}
+}
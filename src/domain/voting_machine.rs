@@ -1,39 +1,996 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use super::{
-	ballot_paper::BallotPaper, generic_domains::AttendenceSheet, scoreboard::Scoreboard,
+	ballot_box::BallotBox,
+	ballot_paper::{BallotPaper, Choice},
+	candidate_change::{AuditEntry, CandidateOutcome, WithdrawalPolicy},
+	counted_ballot::CountedBallot,
+	election_mode::ElectionMode,
+	election_state::{ElectionState, TransitionOutcome},
+	electoral_roll::{ElectoralRoll, Turnout},
+	generic_domains::{AttendenceSheet, Candidate, Score, Voter},
+	identity::{AccentPolicy, CasePolicy, IdentityPolicy},
+	instant_runoff::InstantRunoff,
+	proxy::{Proxies, ProxyOutcome},
+	receipt::{Receipt, ReceiptedVote},
+	recount::{Recount, Tally},
+	results::{Majority, Results},
+	round::{ArchivedRound, RoundOutcome},
+	schedule::Schedule,
+	schulze::Schulze,
+	scoreboard::Scoreboard,
+	single_transferable_vote::SingleTransferableVote,
+	tie_break::{TieBreak, TieBreakPolicy, TieResolution},
+	vote_log::{Timestamp, VoteLog, VoteSource},
 	vote_outcome::VoteOutcome,
+	voting_event::{EventOutcome, VotingEvent},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VotingMachine {
 	voters: AttendenceSheet,
 	scoreboard: Scoreboard,
+	mode: ElectionMode,
+	state: ElectionState,
+	roll: Option<ElectoralRoll>,
+	proxies: Proxies,
+	max_proxies: usize,
+	ballots: BallotBox,
+	archived_rounds: Vec<ArchivedRound>,
+	tie_break: TieBreakPolicy,
+	tie_breaks: Vec<TieBreak>,
+	revotes: bool,
+	secret: bool,
+	write_ins: bool,
+	identity: IdentityPolicy,
+	counted: BTreeMap<Voter, CountedBallot>,
+	tally: Tally,
+	receipts: BTreeMap<Voter, Receipt>,
+	withdrawn: BTreeSet<Candidate>,
+	audit: Vec<AuditEntry>,
+	vote_log: VoteLog,
+	schedule: Schedule,
 }
 
 impl VotingMachine {
 	#[must_use]
 	pub const fn new(voters: AttendenceSheet, scoreboard: Scoreboard) -> Self {
-		Self { voters, scoreboard }
+		Self {
+			voters,
+			scoreboard,
+			mode: ElectionMode::Plurality,
+			state: ElectionState::Open,
+			roll: None,
+			proxies: Proxies(BTreeMap::new()),
+			max_proxies: 1,
+			ballots: BallotBox(Vec::new()),
+			archived_rounds: Vec::new(),
+			tie_break: TieBreakPolicy::Unresolved,
+			tie_breaks: Vec::new(),
+			revotes: false,
+			secret: false,
+			write_ins: false,
+			identity: IdentityPolicy {
+				case: CasePolicy::Sensitive,
+				accents: AccentPolicy::Kept,
+			},
+			counted: BTreeMap::new(),
+			tally: Tally(Vec::new()),
+			receipts: BTreeMap::new(),
+			withdrawn: BTreeSet::new(),
+			audit: Vec::new(),
+			vote_log: VoteLog(Vec::new()),
+			schedule: Schedule {
+				opens: None,
+				closes: None,
+			},
+		}
+	}
+
+	#[must_use]
+	pub const fn with_mode(mut self, mode: ElectionMode) -> Self {
+		self.mode = mode;
+		self
+	}
+
+	#[must_use]
+	pub const fn with_state(mut self, state: ElectionState) -> Self {
+		self.state = state;
+		self
+	}
+
+	/// Restricts the election to the voters of `roll`.
+	#[must_use]
+	pub fn with_roll(mut self, roll: ElectoralRoll) -> Self {
+		self.roll = Some(roll);
+		self
+	}
+
+	/// Sets how many proxies a single voter may hold.
+	#[must_use]
+	pub const fn with_max_proxies(mut self, max_proxies: usize) -> Self {
+		self.max_proxies = max_proxies;
+		self
+	}
+
+	#[must_use]
+	pub fn with_proxies(mut self, proxies: Proxies) -> Self {
+		self.proxies = proxies;
+		self
+	}
+
+	#[must_use]
+	pub fn with_ballots(mut self, ballots: BallotBox) -> Self {
+		self.ballots = ballots;
+		self
+	}
+
+	#[must_use]
+	pub fn with_archived_rounds(mut self, archived_rounds: Vec<ArchivedRound>) -> Self {
+		self.archived_rounds = archived_rounds;
+		self
+	}
+
+	#[must_use]
+	pub fn with_tie_break(mut self, tie_break: TieBreakPolicy) -> Self {
+		self.tie_break = tie_break;
+		self
+	}
+
+	#[must_use]
+	pub fn with_tie_breaks(mut self, tie_breaks: Vec<TieBreak>) -> Self {
+		self.tie_breaks = tie_breaks;
+		self
+	}
+
+	/// Lets voters replace their ballot until the election closes, which keeps
+	/// the ballot of each voter.
+	#[must_use]
+	pub const fn with_revotes(mut self, revotes: bool) -> Self {
+		self.revotes = revotes;
+		self
+	}
+
+	/// Keeps the choices apart from the voters: no ballot is kept per voter,
+	/// which rules out re-votes, the ballot box is shuffled and outcomes never
+	/// name the chosen candidates.
+	#[must_use]
+	pub const fn with_secret(mut self, secret: bool) -> Self {
+		self.secret = secret;
+		self
+	}
+
+	/// Accepts unknown names as new candidates instead of invalid votes.
+	#[must_use]
+	pub const fn with_write_ins(mut self, write_ins: bool) -> Self {
+		self.write_ins = write_ins;
+		self
+	}
+
+	/// Sets how voter and candidate names are written; `normalized` brings
+	/// the names the machine already holds in line.
+	#[must_use]
+	pub const fn with_identity(mut self, identity: IdentityPolicy) -> Self {
+		self.identity = identity;
+		self
+	}
+
+	/// Writes every voter and candidate name the machine holds the way its
+	/// identity policy says, adding up the scores of candidates that end up
+	/// with the same name. The vote log, the audit trail and the archived
+	/// rounds are records and keep the names as they were.
+	#[must_use]
+	pub fn normalized(mut self) -> Self {
+		let identity = self.identity;
+		let normalize_voter = move |voter: Voter| identity.voter(voter);
+		let normalize_candidate = move |candidate: Candidate| identity.candidate(candidate);
+		let pairs = move |pairs: BTreeMap<Voter, Voter>| -> BTreeMap<Voter, Voter> {
+			pairs
+				.into_iter()
+				.map(|(voter, holder)| (normalize_voter(voter), normalize_voter(holder)))
+				.collect()
+		};
+
+		self.voters = AttendenceSheet(
+			self.voters.0.into_iter().map(normalize_voter).collect(),
+			pairs(self.voters.1),
+		);
+		let mut scores = BTreeMap::new();
+		for (name, score) in std::mem::take(&mut self.scoreboard.scores) {
+			let total: &mut Score = scores.entry(normalize_candidate(name)).or_default();
+			total.0 += score.0;
+		}
+		self.scoreboard.scores = scores;
+		self.scoreboard.profiles = std::mem::take(&mut self.scoreboard.profiles)
+			.into_iter()
+			.map(|(name, profile)| (normalize_candidate(name), profile))
+			.collect();
+		self.roll = self
+			.roll
+			.map(|roll| ElectoralRoll(roll.0.into_iter().map(normalize_voter).collect()));
+		self.proxies = Proxies(pairs(self.proxies.0));
+		self.ballots = BallotBox(
+			self.ballots
+				.0
+				.into_iter()
+				.map(|ranking| ranking.into_iter().map(normalize_candidate).collect())
+				.collect(),
+		);
+		if let TieBreakPolicy::Oldest(birth_dates) = &mut self.tie_break {
+			*birth_dates = std::mem::take(birth_dates)
+				.into_iter()
+				.map(|(name, birth_date)| (normalize_candidate(name), birth_date))
+				.collect();
+		}
+		self.counted = std::mem::take(&mut self.counted)
+			.into_iter()
+			.map(|(voter, counted)| {
+				(
+					normalize_voter(voter),
+					counted.map_candidates(normalize_candidate),
+				)
+			})
+			.collect();
+		self.tally = Tally(
+			std::mem::take(&mut self.tally.0)
+				.into_iter()
+				.map(|counted| counted.map_candidates(normalize_candidate))
+				.collect(),
+		);
+		self.receipts = std::mem::take(&mut self.receipts)
+			.into_iter()
+			.map(|(voter, receipt)| (normalize_voter(voter), receipt))
+			.collect();
+		self.withdrawn = std::mem::take(&mut self.withdrawn)
+			.into_iter()
+			.map(normalize_candidate)
+			.collect();
+		self
+	}
+
+	const fn keeps_ballots(&self) -> bool {
+		self.revotes && !self.secret
+	}
+
+	#[must_use]
+	pub fn with_counted(mut self, counted: BTreeMap<Voter, CountedBallot>) -> Self {
+		self.counted = counted;
+		self
+	}
+
+	#[must_use]
+	pub fn with_vote_log(mut self, vote_log: VoteLog) -> Self {
+		self.vote_log = vote_log;
+		self
+	}
+
+	/// Opens and closes the election at set times, and refuses the ballots
+	/// cast outside of them.
+	#[must_use]
+	pub const fn with_schedule(mut self, schedule: Schedule) -> Self {
+		self.schedule = schedule;
+		self
+	}
+
+	#[must_use]
+	pub fn with_tally(mut self, tally: Tally) -> Self {
+		self.tally = tally;
+		self
+	}
+
+	#[must_use]
+	pub fn with_receipts(mut self, receipts: BTreeMap<Voter, Receipt>) -> Self {
+		self.receipts = receipts;
+		self
+	}
+
+	#[must_use]
+	pub fn with_withdrawn(mut self, withdrawn: BTreeSet<Candidate>) -> Self {
+		self.withdrawn = withdrawn;
+		self
+	}
+
+	#[must_use]
+	pub fn with_audit(mut self, audit: Vec<AuditEntry>) -> Self {
+		self.audit = audit;
+		self
 	}
 
 	pub fn vote(&mut self, ballot_paper: BallotPaper) -> VoteOutcome {
 		let voter = ballot_paper.voter;
-		if !self.voters.0.contains(&voter) {
-			let Some(candidate) = ballot_paper.candidate else {
-				self.scoreboard.blank_score.0 += 1;
-				self.voters.0.insert(voter.clone());
-				return VoteOutcome::BlankVote(voter);
-			};
-			if let Some(score) = self.scoreboard.scores.get_mut(&candidate) {
-				score.0 += 1;
-				self.voters.0.insert(voter.clone());
-				return VoteOutcome::AcceptedVote(voter, candidate);
+		let holder = match self.admit(&voter, ballot_paper.cast_by) {
+			Ok(holder) => holder,
+			Err(outcome) => return outcome,
+		};
+		let previous = if self.voters.0.contains(&voter) {
+			if !self.keeps_ballots() {
+				return VoteOutcome::HasAlreadyVoted(voter);
+			}
+			self.counted.remove(&voter)
+		} else {
+			None
+		};
+		if let Some(previous) = &previous {
+			self.withdraw(previous);
+		}
+		self.sign_in(&voter, holder);
+		let outcome = self.count(voter.clone(), ballot_paper.choice);
+		if let Some(counted) = CountedBallot::from_outcome(&outcome) {
+			if self.secret {
+				self.tally.shuffle_in(counted);
 			} else {
+				self.tally.0.push(counted);
+			}
+		}
+		if self.secret {
+			return VoteOutcome::BallotCast(voter);
+		}
+		if self.keeps_ballots() {
+			if let Some(counted) = CountedBallot::from_outcome(&outcome) {
+				self.counted.insert(voter, counted);
+			}
+		}
+		if previous.is_some() {
+			VoteOutcome::VoteChanged(Box::new(outcome))
+		} else {
+			outcome
+		}
+	}
+
+	/// Checks that `voter` may vote now, in person or through `cast_by`, and
+	/// returns the proxy holder casting the ballot.
+	fn admit(&self, voter: &Voter, cast_by: Option<Voter>) -> Result<Option<Voter>, VoteOutcome> {
+		match self.state {
+			ElectionState::NotOpen => return Err(VoteOutcome::ElectionNotOpen(voter.clone())),
+			ElectionState::Closed => return Err(VoteOutcome::ElectionClosed(voter.clone())),
+			ElectionState::Open => {}
+		}
+		if let Some(roll) = &self.roll {
+			if !roll.0.contains(voter) {
+				return Err(VoteOutcome::NotRegistered(voter.clone()));
+			}
+		}
+		let holder = cast_by.filter(|holder| holder != voter);
+		if let Some(holder) = &holder {
+			if self.proxies.0.get(voter) != Some(holder) {
+				return Err(VoteOutcome::NotProxyHolder(holder.clone(), voter.clone()));
+			}
+		}
+		Ok(holder)
+	}
+
+	/// Marks `voter` on the attendance sheet, along with the holder who voted for them.
+	fn sign_in(&mut self, voter: &Voter, holder: Option<Voter>) {
+		self.voters.0.insert(voter.clone());
+		match holder {
+			Some(holder) => self.voters.1.insert(voter.clone(), holder),
+			None => self.voters.1.remove(voter),
+		};
+	}
+
+	/// Checks the voter of a secret ballot in as `vote` would, without counting
+	/// anything, the ballot being in the tally already.
+	pub fn check_in(&mut self, voter: Voter, cast_by: Option<Voter>) -> VoteOutcome {
+		let holder = match self.admit(&voter, cast_by) {
+			Ok(holder) => holder,
+			Err(outcome) => return outcome,
+		};
+		if self.voters.0.contains(&voter) {
+			return VoteOutcome::HasAlreadyVoted(voter);
+		}
+		self.sign_in(&voter, holder);
+		VoteOutcome::BallotCast(voter)
+	}
+
+	/// Votes like `vote`, and hands `receipt` back when the ballot goes into the count.
+	///
+	/// The receipt replaces the earlier one of the voter on the bulletin board.
+	pub fn cast(&mut self, ballot_paper: BallotPaper, receipt: Receipt) -> ReceiptedVote {
+		let voter = ballot_paper.voter.clone();
+		let outcome = self.vote(ballot_paper);
+		self.hand_receipt(voter, outcome, receipt)
+	}
+
+	fn hand_receipt(
+		&mut self,
+		voter: Voter,
+		outcome: VoteOutcome,
+		receipt: Receipt,
+	) -> ReceiptedVote {
+		let counted = matches!(outcome, VoteOutcome::BallotCast(_))
+			|| CountedBallot::from_outcome(&outcome).is_some();
+		let receipt = counted.then(|| {
+			self.receipts.insert(voter, receipt.clone());
+			receipt
+		});
+		ReceiptedVote { outcome, receipt }
+	}
+
+	/// Takes in the ballot of `voter` with `cast`, unless the schedule refuses
+	/// it at `timestamp`, and logs the attempt.
+	fn receive(
+		&mut self,
+		voter: &Voter,
+		timestamp: Timestamp,
+		source: VoteSource,
+		cast: impl FnOnce(&mut Self) -> ReceiptedVote,
+	) -> EventOutcome {
+		let vote = match self.schedule.refuse(voter, timestamp) {
+			Some(outcome) => ReceiptedVote {
+				outcome,
+				receipt: None,
+			},
+			None => cast(self),
+		};
+		self.log_vote(timestamp, source, vote.outcome.clone());
+		EventOutcome::Vote(vote)
+	}
+
+	/// Runs the operation `event` stands for, as it ran when it was recorded.
+	pub fn apply(&mut self, event: VotingEvent) -> EventOutcome {
+		match event {
+			VotingEvent::BallotCast {
+				ballot_paper,
+				receipt,
+				timestamp,
+				source,
+			} => {
+				let voter = ballot_paper.voter.clone();
+				self.receive(&voter, timestamp, source, |machine| {
+					machine.cast(ballot_paper, receipt)
+				})
+			}
+			VotingEvent::CheckedIn {
+				voter,
+				cast_by,
+				receipt,
+				timestamp,
+				source,
+			} => self.receive(&voter.clone(), timestamp, source, |machine| {
+				let outcome = machine.check_in(voter.clone(), cast_by);
+				machine.hand_receipt(voter, outcome, receipt)
+			}),
+			VotingEvent::ProxyRegistered(voter, holder) => {
+				EventOutcome::Proxy(self.register_proxy(voter, holder))
+			}
+			VotingEvent::ElectionOpened => EventOutcome::Transition(self.open()),
+			VotingEvent::ElectionClosed => EventOutcome::Transition(self.close()),
+			VotingEvent::RoundClosed => EventOutcome::Round(self.close_round()),
+			VotingEvent::CandidateAdded(candidate) => {
+				EventOutcome::Candidate(self.add_candidate(candidate))
+			}
+			VotingEvent::CandidateWithdrawn(candidate, policy) => {
+				EventOutcome::Candidate(self.withdraw_candidate(candidate, policy))
+			}
+			VotingEvent::CandidateRenamed(candidate, new_name) => {
+				EventOutcome::Candidate(self.rename_candidate(candidate, new_name))
+			}
+		}
+	}
+
+	/// Appends the outcome of a vote attempt to the vote log.
+	pub fn log_vote(&mut self, timestamp: Timestamp, source: VoteSource, outcome: VoteOutcome) {
+		self.vote_log.append(timestamp, source, outcome);
+	}
+
+	fn count(&mut self, voter: Voter, choice: Choice) -> VoteOutcome {
+		let choice = if self.write_ins {
+			choice.map_candidates(|candidate| self.write_in(candidate))
+		} else {
+			choice
+		};
+		match self.mode {
+			ElectionMode::Plurality | ElectionMode::TwoRound => self.vote_plurality(voter, choice),
+			ElectionMode::InstantRunoff
+			| ElectionMode::Schulze
+			| ElectionMode::SingleTransferableVote { .. } => self.vote_ranked(voter, choice.preferences()),
+			ElectionMode::Approval => self.vote_approval(voter, choice.preferences()),
+		}
+	}
+
+	/// Takes a ballot counted earlier back out of the scoreboard.
+	fn withdraw(&mut self, counted: &CountedBallot) {
+		self.tally.take_back(counted);
+		let candidates = match counted {
+			CountedBallot::Blank => {
+				self.scoreboard.blank_score.0 -= 1;
+				return;
+			}
+			CountedBallot::Invalid => {
+				self.scoreboard.invalid_score.0 -= 1;
+				return;
+			}
+			CountedBallot::Candidate(candidate) => std::slice::from_ref(candidate),
+			CountedBallot::Ranked(ranking) => {
+				if let Some(position) = self.ballots.0.iter().position(|ballot| ballot == ranking) {
+					self.ballots.0.remove(position);
+				}
+				&ranking[..1]
+			}
+			CountedBallot::Approved(approved) => approved.as_slice(),
+		};
+		for candidate in candidates {
+			if let Some(score) = self.scoreboard.scores.get_mut(candidate) {
+				score.0 -= 1;
+			}
+		}
+	}
+
+	/// Lets `holder` cast the ballot of `voter`.
+	pub fn register_proxy(&mut self, voter: Voter, holder: Voter) -> ProxyOutcome {
+		if self.state == ElectionState::Closed {
+			return ProxyOutcome::ElectionClosed;
+		}
+		if voter == holder {
+			return ProxyOutcome::SameVoter(voter);
+		}
+		if let Some(roll) = &self.roll {
+			for registered in [&voter, &holder] {
+				if !roll.0.contains(registered) {
+					return ProxyOutcome::NotRegistered(registered.clone());
+				}
+			}
+		}
+		if self.voters.0.contains(&voter) {
+			return ProxyOutcome::HasAlreadyVoted(voter);
+		}
+		if self.proxies.0.contains_key(&voter) {
+			return ProxyOutcome::AlreadyGiven(voter);
+		}
+		if self.proxies.held_by(&holder) >= self.max_proxies {
+			return ProxyOutcome::TooManyProxies(holder);
+		}
+		self.proxies.0.insert(voter.clone(), holder.clone());
+		ProxyOutcome::Registered(voter, holder)
+	}
+
+	pub fn open(&mut self) -> TransitionOutcome {
+		if self.state != ElectionState::NotOpen {
+			return TransitionOutcome::InvalidTransition(self.state);
+		}
+		self.state = ElectionState::Open;
+		TransitionOutcome::Opened
+	}
+
+	pub fn close(&mut self) -> TransitionOutcome {
+		if self.state != ElectionState::Open {
+			return TransitionOutcome::InvalidTransition(self.state);
+		}
+		let counted_on_scoreboard = matches!(
+			self.mode,
+			ElectionMode::Plurality | ElectionMode::TwoRound | ElectionMode::Approval
+		);
+		if let (true, Majority::Tie(tied)) = (counted_on_scoreboard, self.results().majority) {
+			if let TieResolution::Runoff(candidates) = self.break_tie(tied).resolution {
+				return TransitionOutcome::Runoff(candidates);
+			}
+		}
+		self.state = ElectionState::Closed;
+		TransitionOutcome::Closed
+	}
+
+	/// Closes the current round of a two-round election. Without an absolute
+	/// majority in the first round, the two leading candidates qualify: the
+	/// first round is archived and a fresh round starts between them. The
+	/// election closes once a candidate is elected.
+	pub fn close_round(&mut self) -> RoundOutcome {
+		let outcome = self.settle_round();
+		if matches!(outcome, RoundOutcome::Elected(_)) {
+			self.state = ElectionState::Closed;
+		}
+		outcome
+	}
+
+	fn settle_round(&mut self) -> RoundOutcome {
+		if self.mode != ElectionMode::TwoRound {
+			return RoundOutcome::NotTwoRound;
+		}
+		let ranking: Vec<(Candidate, Score)> = self
+			.scoreboard
+			.ranking()
+			.into_iter()
+			.filter(|(candidate, _)| !self.withdrawn.contains(candidate))
+			.collect();
+		let Some((leader, leader_score)) = ranking.first().cloned() else {
+			return RoundOutcome::Tie(Vec::new());
+		};
+		if leader_score.0 * 2 > self.scoreboard.valid_votes() {
+			return RoundOutcome::Elected(leader);
+		}
+		if self.archived_rounds.is_empty() && ranking.len() >= 2 {
+			return self.qualify(&ranking);
+		}
+		let tied: Vec<Candidate> = ranking
+			.into_iter()
+			.filter(|(_, score)| *score == leader_score)
+			.map(|(candidate, _)| candidate)
+			.collect();
+		if tied.len() == 1 {
+			return RoundOutcome::Elected(leader);
+		}
+		let TieBreak { tied, resolution } = self.break_tie(tied);
+		match resolution {
+			TieResolution::Winner(winner) => RoundOutcome::Elected(winner),
+			TieResolution::Runoff(candidates) => RoundOutcome::Runoff(candidates),
+			TieResolution::Unresolved => RoundOutcome::Tie(tied),
+		}
+	}
+
+	/// Qualifies the two leading candidates of the first round for the second.
+	/// Candidates tied for a qualifying place are settled with the tie-break
+	/// policy, a runoff policy letting all of them through.
+	fn qualify(&mut self, ranking: &[(Candidate, Score)]) -> RoundOutcome {
+		let cutoff = ranking[1].1;
+		let mut qualified: Vec<Candidate> = ranking
+			.iter()
+			.filter(|(_, score)| *score > cutoff)
+			.map(|(candidate, _)| candidate.clone())
+			.collect();
+		let mut tied: Vec<Candidate> = ranking
+			.iter()
+			.filter(|(_, score)| *score == cutoff)
+			.map(|(candidate, _)| candidate.clone())
+			.collect();
+		if qualified.len() + tied.len() == 2 {
+			qualified.append(&mut tied);
+		}
+		while qualified.len() < 2 {
+			let TieBreak {
+				tied: all,
+				resolution,
+			} = self.settle_tie(tied.clone());
+			match resolution {
+				TieResolution::Winner(winner) => {
+					tied.retain(|candidate| *candidate != winner);
+					qualified.push(winner);
+				}
+				TieResolution::Runoff(candidates) => qualified.extend(candidates),
+				TieResolution::Unresolved => return RoundOutcome::Tie(all),
+			}
+		}
+		self.start_round(qualified.clone());
+		match <[Candidate; 2]>::try_from(qualified) {
+			Ok([first, second]) => RoundOutcome::SecondRound(first, second),
+			Err(qualified) => RoundOutcome::Runoff(qualified),
+		}
+	}
+
+	/// Settles a tie with the tie-break policy and records the resolution,
+	/// unless the policy left the tie as is.
+	fn settle_tie(&mut self, tied: Vec<Candidate>) -> TieBreak {
+		let tie_break = self.tie_break.resolve(tied);
+		if tie_break.resolution != TieResolution::Unresolved {
+			self.tie_breaks.push(tie_break.clone());
+		}
+		tie_break
+	}
+
+	/// Settles a shared lead, starting a round between the tied candidates
+	/// when the policy calls for a runoff.
+	fn break_tie(&mut self, tied: Vec<Candidate>) -> TieBreak {
+		let tie_break = self.settle_tie(tied);
+		if let TieResolution::Runoff(candidates) = &tie_break.resolution {
+			self.start_round(candidates.clone());
+		}
+		tie_break
+	}
+
+	/// Archives the current round and starts a fresh one between `candidates`.
+	fn start_round(&mut self, candidates: Vec<Candidate>) {
+		let scoreboard = Scoreboard::new(candidates);
+		self.ballots = BallotBox::default();
+		self.counted.clear();
+		self.tally.0.clear();
+		self.receipts.clear();
+		self.archived_rounds.push(ArchivedRound {
+			voters: std::mem::take(&mut self.voters),
+			scoreboard: std::mem::replace(&mut self.scoreboard, scoreboard),
+		});
+	}
+
+	fn vote_plurality(&mut self, voter: Voter, choice: Choice) -> VoteOutcome {
+		let candidate = match choice {
+			Choice::Single(candidate) => candidate,
+			choice => {
+				let mut candidates = choice.preferences();
+				if candidates.len() > 1 {
+					self.scoreboard.invalid_score.0 += 1;
+					return VoteOutcome::InvalidVote(voter);
+				}
+				candidates.pop()
+			}
+		};
+		let Some(candidate) = candidate else {
+			self.scoreboard.blank_score.0 += 1;
+			return VoteOutcome::BlankVote(voter);
+		};
+		if self.admits(&candidate) {
+			self.scoreboard
+				.scores
+				.entry(candidate.clone())
+				.or_default()
+				.0 += 1;
+			VoteOutcome::AcceptedVote(voter, candidate)
+		} else {
+			self.scoreboard.invalid_score.0 += 1;
+			VoteOutcome::InvalidVote(voter)
+		}
+	}
+
+	fn vote_ranked(&mut self, voter: Voter, ranking: Vec<Candidate>) -> VoteOutcome {
+		let Some(first) = ranking.first() else {
+			self.scoreboard.blank_score.0 += 1;
+			return VoteOutcome::BlankVote(voter);
+		};
+		let distinct: BTreeSet<&Candidate> = ranking.iter().collect();
+		let all_known = ranking.iter().all(|candidate| self.admits(candidate));
+		if distinct.len() != ranking.len() || !all_known {
+			self.scoreboard.invalid_score.0 += 1;
+			return VoteOutcome::InvalidVote(voter);
+		}
+		for candidate in &ranking {
+			self.scoreboard.scores.entry(candidate.clone()).or_default();
+		}
+		if let Some(score) = self.scoreboard.scores.get_mut(first) {
+			score.0 += 1;
+		}
+		if self.secret {
+			self.ballots.shuffle_in(ranking.clone());
+		} else {
+			self.ballots.0.push(ranking.clone());
+		}
+		VoteOutcome::AcceptedRankedVote(voter, ranking)
+	}
+
+	fn vote_approval(&mut self, voter: Voter, candidates: Vec<Candidate>) -> VoteOutcome {
+		if candidates.is_empty() {
+			self.scoreboard.blank_score.0 += 1;
+			return VoteOutcome::BlankVote(voter);
+		}
+		let candidates: BTreeSet<Candidate> = candidates.into_iter().collect();
+		let (approved, unknown): (Vec<Candidate>, Vec<Candidate>) = candidates
+			.into_iter()
+			.partition(|candidate| self.admits(candidate));
+		if approved.is_empty() {
+			self.scoreboard.invalid_score.0 += 1;
+			return VoteOutcome::InvalidVote(voter);
+		}
+		for candidate in &approved {
+			self.scoreboard
+				.scores
+				.entry(candidate.clone())
+				.or_default()
+				.0 += 1;
+		}
+		VoteOutcome::AcceptedApprovalVote(voter, approved, unknown)
+	}
+
+	/// Tells whether `candidate` stands and has not been withdrawn.
+	fn is_running(&self, candidate: &Candidate) -> bool {
+		self.scoreboard.scores.contains_key(candidate) && !self.withdrawn.contains(candidate)
+	}
+
+	/// Tells whether a ballot may name `candidate`: a running candidate, or a
+	/// new name when write-ins are accepted.
+	fn admits(&self, candidate: &Candidate) -> bool {
+		self.is_running(candidate)
+			|| (self.write_ins && !self.scoreboard.scores.contains_key(candidate))
+	}
+
+	/// Returns the candidate a written name stands for under the identity
+	/// policy: a known candidate whose name normalises the same, or else the
+	/// normalised name.
+	fn write_in(&self, candidate: Candidate) -> Candidate {
+		if self.scoreboard.scores.contains_key(&candidate) {
+			return candidate;
+		}
+		let normalized = self.identity.candidate(candidate);
+		self.scoreboard
+			.scores
+			.keys()
+			.find(|known| self.identity.normalize(&known.0) == normalized.0)
+			.cloned()
+			.unwrap_or(normalized)
+	}
+
+	const fn keeps_ranked_ballots(&self) -> bool {
+		matches!(
+			self.mode,
+			ElectionMode::InstantRunoff
+				| ElectionMode::Schulze
+				| ElectionMode::SingleTransferableVote { .. }
+		)
+	}
+
+	/// Adds a candidate, which is only possible before the election opens.
+	pub fn add_candidate(&mut self, candidate: Candidate) -> CandidateOutcome {
+		if self.state != ElectionState::NotOpen {
+			return CandidateOutcome::InvalidState(self.state);
+		}
+		if self.scoreboard.scores.contains_key(&candidate) {
+			return CandidateOutcome::AlreadyExists(candidate);
+		}
+		self.scoreboard
+			.scores
+			.insert(candidate.clone(), Score::default());
+		self.audit
+			.push(AuditEntry::CandidateAdded(candidate.clone()));
+		CandidateOutcome::Added(candidate)
+	}
+
+	/// Withdraws a candidate for good, applying `policy` to the ballots
+	/// already cast for them. A candidate kept on the scoreboard takes no new
+	/// vote and is left out when the winner is picked.
+	pub fn withdraw_candidate(
+		&mut self,
+		candidate: Candidate,
+		policy: WithdrawalPolicy,
+	) -> CandidateOutcome {
+		if self.state == ElectionState::Closed {
+			return CandidateOutcome::InvalidState(self.state);
+		}
+		if !self.is_running(&candidate) {
+			return CandidateOutcome::UnknownCandidate(candidate);
+		}
+		match policy {
+			WithdrawalPolicy::Keep => {
+				self.withdrawn.insert(candidate.clone());
+			}
+			WithdrawalPolicy::Invalidate | WithdrawalPolicy::Redistribute
+				if self.keeps_ranked_ballots() =>
+			{
+				self.strike_from_ballots(&candidate, policy == WithdrawalPolicy::Invalidate);
+			}
+			WithdrawalPolicy::Invalidate if self.mode != ElectionMode::Approval => {
+				let score = self
+					.scoreboard
+					.scores
+					.remove(&candidate)
+					.unwrap_or_default();
+				self.scoreboard.invalid_score.0 += score.0;
+				for counted in self.counted.values_mut().chain(&mut self.tally.0) {
+					if *counted == CountedBallot::Candidate(candidate.clone()) {
+						*counted = CountedBallot::Invalid;
+					}
+				}
+			}
+			WithdrawalPolicy::Invalidate | WithdrawalPolicy::Redistribute => {
+				return CandidateOutcome::PolicyUnavailable(policy);
+			}
+		}
+		self.audit
+			.push(AuditEntry::CandidateWithdrawn(candidate.clone(), policy));
+		CandidateOutcome::Withdrawn(candidate, policy)
+	}
+
+	/// Takes `candidate` off every ranked ballot. With `invalidate`, the ballots
+	/// naming them become invalid; otherwise they move on to their next
+	/// preference and the ones left empty turn blank.
+	fn strike_from_ballots(&mut self, candidate: &Candidate, invalidate: bool) {
+		self.scoreboard.scores.remove(candidate);
+		for ballot in std::mem::take(&mut self.ballots.0) {
+			if !ballot.contains(candidate) {
+				self.ballots.0.push(ballot);
+				continue;
+			}
+			if invalidate {
+				if let Some(score) = ballot
+					.first()
+					.and_then(|first| self.scoreboard.scores.get_mut(first))
+				{
+					score.0 -= 1;
+				}
 				self.scoreboard.invalid_score.0 += 1;
-				self.voters.0.insert(voter.clone());
-				return VoteOutcome::InvalidVote(voter);
+				continue;
+			}
+			let was_first = ballot.first() == Some(candidate);
+			let remaining: Vec<Candidate> = ballot
+				.into_iter()
+				.filter(|ranked| ranked != candidate)
+				.collect();
+			match remaining.first() {
+				None => self.scoreboard.blank_score.0 += 1,
+				Some(next) => {
+					if let Some(score) = self.scoreboard.scores.get_mut(next).filter(|_| was_first)
+					{
+						score.0 += 1;
+					}
+					self.ballots.0.push(remaining);
+				}
+			}
+		}
+		for counted in self.counted.values_mut().chain(&mut self.tally.0) {
+			let CountedBallot::Ranked(ranking) = counted else {
+				continue;
+			};
+			if !ranking.contains(candidate) {
+				continue;
+			}
+			ranking.retain(|ranked| ranked != candidate);
+			if invalidate {
+				*counted = CountedBallot::Invalid;
+			} else if ranking.is_empty() {
+				*counted = CountedBallot::Blank;
 			}
 		}
-		VoteOutcome::HasAlreadyVoted(voter)
+	}
+
+	/// Renames a candidate everywhere it appears, until the election closes.
+	pub fn rename_candidate(
+		&mut self,
+		candidate: Candidate,
+		new_name: Candidate,
+	) -> CandidateOutcome {
+		if self.state == ElectionState::Closed {
+			return CandidateOutcome::InvalidState(self.state);
+		}
+		let Some(score) = self.scoreboard.scores.remove(&candidate) else {
+			return CandidateOutcome::UnknownCandidate(candidate);
+		};
+		if self.scoreboard.scores.contains_key(&new_name) {
+			self.scoreboard.scores.insert(candidate, score);
+			return CandidateOutcome::AlreadyExists(new_name);
+		}
+		self.scoreboard.scores.insert(new_name.clone(), score);
+		if let Some(profile) = self.scoreboard.profiles.remove(&candidate) {
+			self.scoreboard.profiles.insert(new_name.clone(), profile);
+		}
+		let rename = |ranked: &mut Candidate| {
+			if *ranked == candidate {
+				ranked.clone_from(&new_name);
+			}
+		};
+		self.ballots.0.iter_mut().flatten().for_each(rename);
+		for counted in self.counted.values_mut().chain(&mut self.tally.0) {
+			match counted {
+				CountedBallot::Candidate(chosen) => rename(chosen),
+				CountedBallot::Ranked(candidates) | CountedBallot::Approved(candidates) => {
+					candidates.iter_mut().for_each(rename);
+				}
+				CountedBallot::Blank | CountedBallot::Invalid => {}
+			}
+		}
+		if self.withdrawn.remove(&candidate) {
+			self.withdrawn.insert(new_name.clone());
+		}
+		if let TieBreakPolicy::Oldest(birth_dates) = &mut self.tie_break {
+			if let Some(birth_date) = birth_dates.remove(&candidate) {
+				birth_dates.insert(new_name.clone(), birth_date);
+			}
+		}
+		self.audit.push(AuditEntry::CandidateRenamed(
+			candidate.clone(),
+			new_name.clone(),
+		));
+		CandidateOutcome::Renamed(candidate, new_name)
+	}
+
+	/// Computes the results of the scoreboard, where withdrawn candidates
+	/// keep their votes but cannot win.
+	#[must_use]
+	pub fn results(&self) -> Results {
+		self.scoreboard.results().with_withdrawn(&self.withdrawn)
+	}
+
+	#[must_use]
+	pub fn instant_runoff(&self) -> InstantRunoff {
+		self.scoreboard
+			.instant_runoff(&self.ballots, &self.withdrawn)
+	}
+
+	#[must_use]
+	pub fn schulze(&self) -> Schulze {
+		self.scoreboard.schulze(&self.ballots, &self.withdrawn)
+	}
+
+	#[must_use]
+	pub fn single_transferable_vote(&self) -> SingleTransferableVote {
+		let seats = match self.mode {
+			ElectionMode::SingleTransferableVote { seats } => seats,
+			_ => 1,
+		};
+		self.scoreboard
+			.single_transferable_vote(&self.ballots, seats, &self.withdrawn)
 	}
 
 	#[must_use]
@@ -45,4 +1002,137 @@ impl VotingMachine {
 	pub const fn get_voter(&self) -> &AttendenceSheet {
 		&self.voters
 	}
+
+	#[must_use]
+	pub fn get_archived_rounds(&self) -> &[ArchivedRound] {
+		&self.archived_rounds
+	}
+
+	/// Returns the number of the round in progress, starting at 1.
+	#[must_use]
+	pub fn get_round(&self) -> usize {
+		self.archived_rounds.len() + 1
+	}
+
+	#[must_use]
+	pub const fn get_roll(&self) -> Option<&ElectoralRoll> {
+		self.roll.as_ref()
+	}
+
+	/// Returns how many registered voters took part, when the election has a roll.
+	#[must_use]
+	pub fn turnout(&self) -> Option<Turnout> {
+		self.roll.as_ref().map(|roll| Turnout {
+			voted: self.voters.0.len(),
+			registered: roll.0.len(),
+		})
+	}
+
+	#[must_use]
+	pub const fn get_proxies(&self) -> &Proxies {
+		&self.proxies
+	}
+
+	#[must_use]
+	pub const fn get_max_proxies(&self) -> usize {
+		self.max_proxies
+	}
+
+	#[must_use]
+	pub const fn get_state(&self) -> ElectionState {
+		self.state
+	}
+
+	#[must_use]
+	pub const fn get_mode(&self) -> ElectionMode {
+		self.mode
+	}
+
+	#[must_use]
+	pub const fn get_tie_break(&self) -> &TieBreakPolicy {
+		&self.tie_break
+	}
+
+	/// Returns every tie settled so far, oldest first.
+	#[must_use]
+	pub fn get_tie_breaks(&self) -> &[TieBreak] {
+		&self.tie_breaks
+	}
+
+	#[must_use]
+	pub const fn get_revotes(&self) -> bool {
+		self.revotes
+	}
+
+	#[must_use]
+	pub const fn get_vote_log(&self) -> &VoteLog {
+		&self.vote_log
+	}
+
+	#[must_use]
+	pub const fn get_tally(&self) -> &Tally {
+		&self.tally
+	}
+
+	/// Tallies the ballots of the round again and compares the result with
+	/// the scoreboard.
+	#[must_use]
+	pub fn recount(&self) -> Recount {
+		self.tally.recount(&self.scoreboard)
+	}
+
+	#[must_use]
+	pub const fn get_schedule(&self) -> &Schedule {
+		&self.schedule
+	}
+
+	#[must_use]
+	pub const fn get_receipts(&self) -> &BTreeMap<Voter, Receipt> {
+		&self.receipts
+	}
+
+	/// Lists the receipts of the ballots in the count, sorted so the listing
+	/// says nothing about who voted when.
+	#[must_use]
+	pub fn bulletin_board(&self) -> Vec<&Receipt> {
+		let mut board: Vec<&Receipt> = self.receipts.values().collect();
+		board.sort();
+		board
+	}
+
+	#[must_use]
+	pub const fn get_write_ins(&self) -> bool {
+		self.write_ins
+	}
+
+	#[must_use]
+	pub const fn get_identity(&self) -> &IdentityPolicy {
+		&self.identity
+	}
+
+	#[must_use]
+	pub const fn get_secret(&self) -> bool {
+		self.secret
+	}
+
+	/// Returns the ballot of each voter, only kept when re-votes are allowed.
+	#[must_use]
+	pub const fn get_counted(&self) -> &BTreeMap<Voter, CountedBallot> {
+		&self.counted
+	}
+
+	#[must_use]
+	pub const fn get_withdrawn(&self) -> &BTreeSet<Candidate> {
+		&self.withdrawn
+	}
+
+	#[must_use]
+	pub fn get_audit(&self) -> &[AuditEntry] {
+		&self.audit
+	}
+
+	#[must_use]
+	pub const fn get_ballots(&self) -> &BallotBox {
+		&self.ballots
+	}
 }
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use super::{
+	generic_domains::{Candidate, QuestionId},
+	vote_log::Timestamp,
+	voting_event::{EventOutcome, RecordedEvent},
+	voting_machine::VotingMachine,
+};
+
+/// Every question put to the vote during a session, each with its own machine.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VotingSession {
+	questions: BTreeMap<QuestionId, VotingMachine>,
+}
+
+impl VotingSession {
+	/// Creates a session holding `machine` as its main question.
+	#[must_use]
+	pub fn new(machine: VotingMachine) -> Self {
+		let mut questions = BTreeMap::new();
+		questions.insert(QuestionId::main(), machine);
+		Self { questions }
+	}
+
+	#[must_use]
+	pub fn with_question(mut self, question: QuestionId, machine: VotingMachine) -> Self {
+		self.questions.insert(question, machine);
+		self
+	}
+
+	#[must_use]
+	pub fn get_question(&self, question: &QuestionId) -> Option<&VotingMachine> {
+		self.questions.get(question)
+	}
+
+	pub fn get_question_mut(&mut self, question: &QuestionId) -> Option<&mut VotingMachine> {
+		self.questions.get_mut(question)
+	}
+
+	/// Applies `recorded` to its question, or returns `None` when the session has no such question.
+	pub fn apply(&mut self, recorded: RecordedEvent) -> Option<EventOutcome> {
+		let machine = self.questions.get_mut(&recorded.question)?;
+		Some(machine.apply(recorded.event))
+	}
+
+	/// Applies to each question the scheduled transitions it is owed at `now`,
+	/// so that a deadline shows before the next event records it.
+	pub fn run_due(&mut self, now: Timestamp) {
+		for machine in self.questions.values_mut() {
+			for event in machine.get_schedule().due(machine.get_state(), now) {
+				machine.apply(event);
+			}
+		}
+	}
+
+	#[must_use]
+	pub const fn get_questions(&self) -> &BTreeMap<QuestionId, VotingMachine> {
+		&self.questions
+	}
+
+	/// Tells whether a question of the session is voted by secret ballot.
+	#[must_use]
+	pub fn has_secret_ballot(&self) -> bool {
+		self.questions.values().any(VotingMachine::get_secret)
+	}
+
+	/// Returns the candidates of each question, in order.
+	#[must_use]
+	pub fn get_candidates(&self) -> BTreeMap<QuestionId, Vec<Candidate>> {
+		self.questions
+			.iter()
+			.map(|(question, machine)| {
+				let candidates = machine.get_scoreboard().scores.keys().cloned().collect();
+				(question.clone(), candidates)
+			})
+			.collect()
+	}
+}
+
+impl From<VotingMachine> for VotingSession {
+	fn from(value: VotingMachine) -> Self {
+		Self::new(value)
+	}
+}
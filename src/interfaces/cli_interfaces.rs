@@ -1,17 +1,37 @@
 use super::lexicon::Lexicon;
-use super::show_vote_outcome;
+use super::{
+	show_audit_entry, show_candidate_outcome, show_election_state, show_last_place_tie,
+	show_majority, show_proxy_outcome, show_receipted_vote, show_tie_break,
+	show_transition_outcome, show_verification,
+};
 use crate::domain::ballot_paper::BallotPaper;
+use crate::domain::candidate_change::AuditEntry;
+use crate::domain::election_mode::ElectionMode;
 use crate::domain::generic_domains::AttendenceSheet;
 use crate::domain::generic_domains::Candidate;
+use crate::domain::generic_domains::QuestionId;
 use crate::domain::generic_domains::Voter;
+use crate::domain::instant_runoff::InstantRunoff;
+use crate::domain::recount::{Recount, TallyLine};
+use crate::domain::results::Results;
+use crate::domain::round::RoundOutcome;
+use crate::domain::schulze::Schulze;
 use crate::domain::scoreboard::Scoreboard;
-use crate::domain::vote_outcome::VoteOutcome;
-use crate::{storage::Storage, use_cases::VotingController};
+use crate::domain::single_transferable_vote::SingleTransferableVote;
+use crate::domain::voting_event::VotingEvent;
+use crate::domain::voting_session::VotingSession;
+use crate::{
+	storage::Storage,
+	use_cases::{CandidateForm, PolicyField, ProxyForm, VotingController},
+};
 
 fn show_attendence_sheet(voters: &AttendenceSheet, lexicon: &Lexicon) -> String {
 	let mut res = lexicon.voters_title.to_string();
 	for voter in voters.0.clone() {
-		res += &format!("- {voter}\n");
+		match voters.1.get(&voter) {
+			Some(holder) => res += &format!("- {voter} ({} {holder})\n", lexicon.cast_by),
+			None => res += &format!("- {voter}\n"),
+		}
 	}
 	res
 }
@@ -26,36 +46,416 @@ fn show_scoreboard(scores: &Scoreboard, lexicon: &Lexicon) -> String {
 	res
 }
 
+fn show_results(results: &Results, lexicon: &Lexicon) -> String {
+	let mut res = lexicon.results_title.to_string();
+	for result in &results.ranking {
+		let candidate = if result.withdrawn {
+			format!("{} ({})", result.candidate, lexicon.withdrawn)
+		} else {
+			result.candidate.to_string()
+		};
+		res += &format!(
+			"{}. {candidate}: {} ({} {}, {} {})\n",
+			result.rank,
+			result.score,
+			result.of_valid,
+			lexicon.of_valid,
+			result.of_cast,
+			lexicon.of_cast
+		);
+	}
+	res += &show_majority(&results.majority, lexicon);
+	res
+}
+
+fn show_instant_runoff(runoff: &InstantRunoff, lexicon: &Lexicon) -> String {
+	let mut res = String::new();
+	for (number, round) in runoff.rounds.iter().enumerate() {
+		res += &format!("\n{} {}:\n", lexicon.round, number + 1);
+		for (nom, score) in &round.scores {
+			res += &format!("{nom}: {score}\n");
+		}
+		res += &format!("{}: {}", lexicon.exhausted, round.exhausted);
+		if let Some(tie) = &round.last_place_tie {
+			res += &format!("\n{}", show_last_place_tie(tie, lexicon));
+		}
+		if let Some(eliminated) = &round.eliminated {
+			res += &format!("\n{}: {eliminated}", lexicon.eliminated);
+		}
+	}
+	match &runoff.winner {
+		Some(winner) => res += &format!("\n{}: {winner}", lexicon.winner),
+		None => res += &format!("\n{}", lexicon.no_winner),
+	}
+	res
+}
+
+fn show_schulze(schulze: &Schulze, lexicon: &Lexicon) -> String {
+	let mut res = format!("\n{}:", lexicon.pairwise_title);
+	for (row, columns) in &schulze.pairwise {
+		let duels: Vec<String> = columns
+			.iter()
+			.map(|(column, score)| format!("{column} {score}"))
+			.collect();
+		res += &format!("\n{row}: {}", duels.join(", "));
+	}
+	if let Some(winner) = &schulze.condorcet_winner {
+		res += &format!("\n{}: {winner}", lexicon.condorcet_winner);
+	} else {
+		res += &format!("\n{}:", lexicon.schulze_ranking);
+		for (rank, tier) in schulze.ranking.iter().enumerate() {
+			let tier: Vec<String> = tier.iter().map(ToString::to_string).collect();
+			res += &format!("\n{}. {}", rank + 1, tier.join(", "));
+		}
+	}
+	res
+}
+
+fn show_single_transferable_vote(stv: &SingleTransferableVote, lexicon: &Lexicon) -> String {
+	let mut res = format!("\n{}: {}", lexicon.quota, stv.quota);
+	for (number, round) in stv.rounds.iter().enumerate() {
+		res += &format!("\n{} {}:\n", lexicon.round, number + 1);
+		for (nom, votes) in &round.tallies {
+			res += &format!("{nom}: {votes}\n");
+		}
+		res += &format!("{}: {}", lexicon.exhausted, round.exhausted);
+		if let Some(tie) = &round.last_place_tie {
+			res += &format!("\n{}", show_last_place_tie(tie, lexicon));
+		}
+		if !round.elected.is_empty() {
+			let elected: Vec<String> = round.elected.iter().map(ToString::to_string).collect();
+			res += &format!("\n{}: {}", lexicon.elected, elected.join(", "));
+		}
+		if !round.eliminated.is_empty() {
+			let eliminated: Vec<String> =
+				round.eliminated.iter().map(ToString::to_string).collect();
+			res += &format!("\n{}: {}", lexicon.eliminated, eliminated.join(", "));
+		}
+		for transfer in &round.transfers {
+			res += &format!(
+				"\n{} {}: {}",
+				lexicon.transfer, transfer.from, transfer.votes
+			);
+		}
+	}
+	let elected: Vec<String> = stv.elected.iter().map(ToString::to_string).collect();
+	res += &format!("\n{}: {}", lexicon.elected, elected.join(", "));
+	res
+}
+
+/// Shows the outcome of a recount, with the lines that do not match.
+pub fn show_recount(recount: &Recount, lexicon: &Lexicon) -> String {
+	if recount.lacks_ballots() {
+		return lexicon.no_ballots_stored.to_string();
+	}
+	if recount.discrepancies.is_empty() {
+		return format!("{} {}", recount.ballots, lexicon.recount_matches);
+	}
+	let mut res = format!("{} {}", recount.ballots, lexicon.recount_differs);
+	for discrepancy in &recount.discrepancies {
+		let line = match &discrepancy.line {
+			TallyLine::Candidate(candidate) => candidate.0.as_str(),
+			TallyLine::Blank => lexicon.blank,
+			TallyLine::Invalid => lexicon.invalid,
+		};
+		res += &format!(
+			"\n{line}: {} {}, {} {}",
+			discrepancy.stored,
+			lexicon.stored_score,
+			discrepancy.recounted,
+			lexicon.recounted_score
+		);
+	}
+	res
+}
+
+fn show_round_outcome(outcome: &RoundOutcome, lexicon: &Lexicon) -> String {
+	match outcome {
+		RoundOutcome::Elected(candidate) => format!("{}: {candidate}", lexicon.winner),
+		RoundOutcome::SecondRound(first, second) => {
+			format!("{} {first} / {second}", lexicon.second_round)
+		}
+		RoundOutcome::Tie(candidates) => {
+			let candidates: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+			format!("{} {}", lexicon.tie, candidates.join(" / "))
+		}
+		RoundOutcome::Runoff(candidates) => {
+			let candidates: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+			format!("{} {}", lexicon.runoff, candidates.join(" / "))
+		}
+		RoundOutcome::NotTwoRound => lexicon.not_two_round.to_string(),
+	}
+}
+
+fn show_event(event: &VotingEvent, lexicon: &Lexicon) -> String {
+	match event {
+		VotingEvent::BallotCast { .. } => show_event(&event.clone().without_choice(), lexicon),
+		VotingEvent::CheckedIn {
+			voter,
+			cast_by,
+			timestamp,
+			source,
+			..
+		} => {
+			let mut res = format!("{timestamp} {} {voter} ({source})", lexicon.ballot_from);
+			if let Some(holder) = cast_by {
+				res += &format!(" {} {holder}", lexicon.cast_by);
+			}
+			res
+		}
+		VotingEvent::ProxyRegistered(voter, holder) => {
+			format!("{voter} {} {holder}.", lexicon.proxy_given)
+		}
+		VotingEvent::ElectionOpened => lexicon.state_open.to_string(),
+		VotingEvent::ElectionClosed => lexicon.state_closed.to_string(),
+		VotingEvent::RoundClosed => lexicon.round_closed.to_string(),
+		VotingEvent::CandidateAdded(candidate) => {
+			show_audit_entry(&AuditEntry::CandidateAdded(candidate.clone()), lexicon)
+		}
+		VotingEvent::CandidateWithdrawn(candidate, policy) => show_audit_entry(
+			&AuditEntry::CandidateWithdrawn(candidate.clone(), *policy),
+			lexicon,
+		),
+		VotingEvent::CandidateRenamed(candidate, new_name) => show_audit_entry(
+			&AuditEntry::CandidateRenamed(candidate.clone(), new_name.clone()),
+			lexicon,
+		),
+	}
+}
+
+fn ballot_paper(voter: Voter, mut candidats: Vec<Candidate>) -> BallotPaper {
+	if candidats.len() > 1 {
+		BallotPaper::ranked(voter, candidats)
+	} else {
+		BallotPaper::new(voter, candidats.pop())
+	}
+}
+
+fn withdrawal_policy(word: Option<&Candidate>, lexicon: &Lexicon) -> Option<PolicyField> {
+	match word.map(|word| word.0.as_str()) {
+		None => Some(PolicyField::Keep),
+		Some(word) if word == lexicon.policy_keep => Some(PolicyField::Keep),
+		Some(word) if word == lexicon.policy_invalidate => Some(PolicyField::Invalidate),
+		Some(word) if word == lexicon.policy_redistribute => Some(PolicyField::Redistribute),
+		Some(_) => None,
+	}
+}
+
+fn show_questions(session: &VotingSession, lexicon: &Lexicon) -> String {
+	let mut res = lexicon.questions_title.to_string();
+	for (question, machine) in session.get_questions() {
+		let options: Vec<String> = machine
+			.get_scoreboard()
+			.scores
+			.keys()
+			.map(ToString::to_string)
+			.collect();
+		res += &format!("- {question}: {}\n", options.join(", "));
+	}
+	res
+}
+
+/// Runs a command on the main question, or on the question named after a
+/// leading `question <id>`.
 pub async fn handle_line<Store: Storage>(
 	line: &str,
 	controller: VotingController<Store>,
 	lexicon: &Lexicon,
+) -> anyhow::Result<String> {
+	let voting_session = controller.get_voting_session().await?;
+	let mut mots = line.splitn(3, ' ');
+	let premier_mot = mots.next().unwrap_or_default();
+	if premier_mot == lexicon.questions {
+		return Ok(show_questions(&voting_session, lexicon));
+	}
+	if premier_mot != lexicon.question {
+		return handle_command(line, controller, lexicon).await;
+	}
+	let question = QuestionId(mots.next().unwrap_or_default().to_string());
+	if voting_session.get_question(&question).is_none() {
+		return Ok(lexicon.unknown_question.to_string());
+	}
+	handle_command(
+		mots.next().unwrap_or_default(),
+		controller.on_question(question),
+		lexicon,
+	)
+	.await
+}
+
+async fn handle_command<Store: Storage>(
+	line: &str,
+	controller: VotingController<Store>,
+	lexicon: &Lexicon,
 ) -> anyhow::Result<String> {
 	let voting_machine = controller.get_voting_machine().await?;
 	let mut mots = line.split(' ');
 	let premier_mot = mots.next().unwrap_or_default();
 	let deuxieme_mot = mots.next().unwrap_or_default();
-	let troisieme_mot = mots.next().unwrap_or_default();
+	let mut candidats: Vec<Candidate> = mots
+		.filter(|mot| !mot.is_empty())
+		.map(|mot| Candidate(mot.to_string()))
+		.collect();
 	let res = if premier_mot == lexicon.vote {
-		if !deuxieme_mot.is_empty() && !troisieme_mot.is_empty() {
-			let ballot_paper = BallotPaper {
-				voter: Voter(deuxieme_mot.to_string()),
-				candidate: Some(Candidate(troisieme_mot.to_string())),
-			};
-			show_vote_outcome(controller.clone().vote(ballot_paper.into()).await?, lexicon)
-		} else if !deuxieme_mot.is_empty() {
-			let ballot_paper = BallotPaper {
-				voter: Voter(deuxieme_mot.to_string()),
-				candidate: None,
-			};
-			show_vote_outcome(controller.clone().vote(ballot_paper.into()).await?, lexicon)
-		} else {
+		if deuxieme_mot.is_empty() {
 			lexicon.candidate_missing.to_string()
+		} else {
+			let ballot_paper = ballot_paper(Voter(deuxieme_mot.to_string()), candidats);
+			show_receipted_vote(controller.clone().vote(ballot_paper.into()).await?, lexicon)
+		}
+	} else if premier_mot == lexicon.proxy_vote {
+		if deuxieme_mot.is_empty() || candidats.is_empty() {
+			lexicon.proxy_missing.to_string()
+		} else {
+			let voter = Voter(candidats.remove(0).0);
+			let ballot_paper =
+				ballot_paper(voter, candidats).cast_by(Voter(deuxieme_mot.to_string()));
+			show_receipted_vote(controller.clone().vote(ballot_paper.into()).await?, lexicon)
+		}
+	} else if premier_mot == lexicon.proxy {
+		match candidats.first() {
+			Some(holder) if !deuxieme_mot.is_empty() => {
+				let proxy_form = ProxyForm {
+					voter: deuxieme_mot.to_string(),
+					holder: holder.0.clone(),
+				};
+				show_proxy_outcome(
+					controller.clone().register_proxy(proxy_form).await?,
+					lexicon,
+				)
+			}
+			_ => lexicon.proxy_missing.to_string(),
 		}
 	} else if premier_mot == lexicon.voters {
 		show_attendence_sheet(voting_machine.get_voter(), lexicon)
 	} else if premier_mot == lexicon.scores {
-		show_scoreboard(voting_machine.get_scoreboard(), lexicon)
+		let mut res = show_scoreboard(voting_machine.get_scoreboard(), lexicon);
+		if let Some(turnout) = voting_machine.turnout() {
+			res += &format!(
+				"\n{}: {}/{}",
+				lexicon.turnout, turnout.voted, turnout.registered
+			);
+		}
+		match voting_machine.get_mode() {
+			ElectionMode::InstantRunoff => {
+				res += &show_instant_runoff(&voting_machine.instant_runoff(), lexicon);
+			}
+			ElectionMode::Schulze => res += &show_schulze(&voting_machine.schulze(), lexicon),
+			ElectionMode::SingleTransferableVote { .. } => {
+				res += &show_single_transferable_vote(
+					&voting_machine.single_transferable_vote(),
+					lexicon,
+				);
+			}
+			ElectionMode::Plurality | ElectionMode::TwoRound | ElectionMode::Approval => {}
+		}
+		res
+	} else if premier_mot == lexicon.results {
+		let voting_machine = match deuxieme_mot.parse::<usize>() {
+			Ok(events) => match controller.get_voting_machine_at(events).await? {
+				Some(voting_machine) => voting_machine,
+				None => return Ok(lexicon.secret_past_results.to_string()),
+			},
+			Err(_) => voting_machine,
+		};
+		let mut res = show_results(&voting_machine.results(), lexicon);
+		for tie_break in voting_machine.get_tie_breaks() {
+			res += &format!("\n{}", show_tie_break(tie_break, lexicon));
+		}
+		res
+	} else if premier_mot == lexicon.add_command && !deuxieme_mot.is_empty() {
+		let candidate_form = CandidateForm {
+			candidate: deuxieme_mot.to_string(),
+			new_name: String::new(),
+			policy: PolicyField::default(),
+		};
+		show_candidate_outcome(
+			controller.clone().add_candidate(candidate_form).await?,
+			lexicon,
+		)
+	} else if premier_mot == lexicon.withdraw_command && !deuxieme_mot.is_empty() {
+		match withdrawal_policy(candidats.first(), lexicon) {
+			Some(policy) => {
+				let candidate_form = CandidateForm {
+					candidate: deuxieme_mot.to_string(),
+					new_name: String::new(),
+					policy,
+				};
+				show_candidate_outcome(
+					controller
+						.clone()
+						.withdraw_candidate(candidate_form)
+						.await?,
+					lexicon,
+				)
+			}
+			None => lexicon.unknown_policy.to_string(),
+		}
+	} else if premier_mot == lexicon.rename_command && !candidats.is_empty() {
+		let candidate_form = CandidateForm {
+			candidate: deuxieme_mot.to_string(),
+			new_name: candidats.remove(0).0,
+			policy: PolicyField::default(),
+		};
+		show_candidate_outcome(
+			controller.clone().rename_candidate(candidate_form).await?,
+			lexicon,
+		)
+	} else if premier_mot == lexicon.audit_command {
+		let mut res = lexicon.audit_title.to_string();
+		for (number, entry) in voting_machine.get_audit().iter().enumerate() {
+			res += &format!("{}. {}\n", number + 1, show_audit_entry(entry, lexicon));
+		}
+		res
+	} else if premier_mot == lexicon.receipts_command {
+		let mut res = lexicon.bulletin_board.to_string();
+		for receipt in voting_machine.bulletin_board() {
+			res += &format!("{receipt}\n");
+		}
+		res
+	} else if premier_mot == lexicon.history_command {
+		let mut res = lexicon.history_title.to_string();
+		for (number, recorded) in controller.get_history().await?.iter().enumerate() {
+			if recorded.question == *controller.get_question() {
+				res += &format!("{}. {}\n", number + 1, show_event(&recorded.event, lexicon));
+			}
+		}
+		res
+	} else if premier_mot == lexicon.verify_command {
+		show_verification(
+			&controller.verify_history().await?,
+			&voting_machine.get_vote_log().verify(),
+			lexicon,
+		)
+	} else if premier_mot == lexicon.recount_command {
+		show_recount(&voting_machine.recount(), lexicon)
+	} else if premier_mot == lexicon.open {
+		show_transition_outcome(controller.clone().open_election().await?, lexicon)
+	} else if premier_mot == lexicon.close {
+		show_transition_outcome(controller.clone().close_election().await?, lexicon)
+	} else if premier_mot == lexicon.state {
+		show_election_state(voting_machine.get_state(), lexicon).to_string()
+	} else if premier_mot == lexicon.close_round {
+		show_round_outcome(&controller.clone().close_round().await?, lexicon)
+	} else if premier_mot == lexicon.round_command {
+		let current = voting_machine.get_round();
+		match deuxieme_mot.parse::<usize>() {
+			Err(_) if deuxieme_mot.is_empty() => format!("{} {current}", lexicon.round),
+			Ok(round) if round == current => format!(
+				"{} {round}:\n{}",
+				lexicon.round,
+				show_scoreboard(voting_machine.get_scoreboard(), lexicon)
+			),
+			Ok(round) if round > 0 && round < current => format!(
+				"{} {round}:\n{}",
+				lexicon.round,
+				show_scoreboard(
+					&voting_machine.get_archived_rounds()[round - 1].scoreboard,
+					lexicon
+				)
+			),
+			_ => lexicon.unknown_round.to_string(),
+		}
 	} else if line.is_empty() {
 		lexicon.help.to_string()
 	} else {
@@ -70,16 +470,34 @@ mod tests {
 
 	use crate::{
 		domain::{
-			generic_domains::{AttendenceSheet, Candidate, Score},
+			election_mode::ElectionMode,
+			election_state::ElectionState,
+			electoral_roll::ElectoralRoll,
+			generic_domains::{AttendenceSheet, Candidate, QuestionId, Score, Voter},
+			identity::{AccentPolicy, CasePolicy, IdentityPolicy},
+			recount::{Discrepancy, Recount, TallyLine},
 			scoreboard::Scoreboard,
 			voting_machine::VotingMachine,
+			voting_session::VotingSession,
+		},
+		interfaces::{
+			cli_interfaces::{handle_line, show_recount},
+			lexicon::Lexicon,
 		},
-		interfaces::{cli_interfaces::handle_line, lexicon::Lexicon},
 		storage::Storage,
 		storages::memory::MemoryStore,
 		use_cases::VotingController,
 	};
 
+	/// Checks that `message` ends with a receipt, and returns what comes before it.
+	fn strip_receipt(message: String, lexicon: &Lexicon) -> String {
+		let (outcome, receipt) = message.rsplit_once('\n').unwrap();
+		let code = receipt.strip_prefix(lexicon.receipt).unwrap().trim();
+		assert_eq!(64, code.len());
+		assert!(code.chars().all(|c| c.is_ascii_hexdigit()));
+		outcome.to_string()
+	}
+
 	#[tokio::test]
 	async fn no_command() {
 		let lexicon = Lexicon::french();
@@ -91,17 +509,19 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = MemoryStore::new(voting_machine).await.unwrap();
-		let mut controller = VotingController::new(memory);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
 
 		assert_eq!(
-			"Aide :\n - voter <nom> [candidat]\n - scores\n - votants".to_string(),
+			"Aide :\n - voter <nom> [candidat...]\n - scores\n - resultats [evenement]\n - votants\n - ouvrir\n - fermer\n - etat\n - cloturer_tour\n - tour [numero]\n - procuration <mandant> <mandataire>\n - voter_procuration <mandataire> <mandant> [candidat...]\n - ajouter <candidat>\n - retirer <candidat> [garder|annuler|reporter]\n - renommer <ancien> <nouveau>\n - journal\n - recus\n - verifier\n - recompter\n - historique\n - questions\n - question <id> <commande>"
+				.to_string(),
 			handle_line("", controller, &lexicon).await.unwrap()
 		);
 	}
@@ -117,14 +537,15 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = MemoryStore::new(voting_machine).await.unwrap();
-		let mut controller = VotingController::new(memory);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
 
 		assert_eq!(
 			"Voici les votants:\n".to_string(),
@@ -143,14 +564,15 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = MemoryStore::new(voting_machine).await.unwrap();
-		let mut controller = VotingController::new(memory);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
 
 		assert_eq!(
 			"Voici les scores:\nYay: 0\nBlanc: 0\nNull: 0".to_string(),
@@ -169,20 +591,24 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = MemoryStore::new(voting_machine).await.unwrap();
-		let mut controller = VotingController::new(memory);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
 
 		assert_eq!(
 			"moi a voter pour MacOS.".to_string(),
-			handle_line("voter moi MacOS", controller, &lexicon)
-				.await
-				.unwrap()
+			strip_receipt(
+				handle_line("voter moi MacOS", controller, &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
 		);
 	}
 
@@ -197,20 +623,24 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = MemoryStore::new(voting_machine).await.unwrap();
-		let mut controller = VotingController::new(memory);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
 
 		assert_eq!(
 			"moi a voter blanc.".to_string(),
-			handle_line("voter moi", controller, &lexicon)
-				.await
-				.unwrap()
+			strip_receipt(
+				handle_line("voter moi", controller, &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
 		);
 	}
 
@@ -225,14 +655,15 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = MemoryStore::new(voting_machine).await.unwrap();
-		let mut controller = VotingController::new(memory);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
 
 		assert_eq!(
 			"Il manque un votant.".to_string(),
@@ -251,18 +682,657 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = MemoryStore::new(voting_machine).await.unwrap();
-		let mut controller = VotingController::new(memory);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
 
 		assert_eq!(
 			"Commande non valide".to_string(),
 			handle_line("zxdfsdf", controller, &lexicon).await.unwrap()
 		);
 	}
+
+	#[tokio::test]
+	async fn show_instant_runoff() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![
+			Candidate("A".to_string()),
+			Candidate("B".to_string()),
+			Candidate("C".to_string()),
+		]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::InstantRunoff);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		for line in [
+			"vote v A B",
+			"vote w A C",
+			"vote x B A",
+			"vote y B C",
+			"vote z C B",
+		] {
+			handle_line(line, controller.clone(), &lexicon)
+				.await
+				.unwrap();
+		}
+
+		assert_eq!(
+			"Scores:\nA: 2\nB: 2\nC: 1\nBlank: 0\nInvalid: 0\n\
+			Round 1:\nA: 2\nB: 2\nC: 1\nExhausted: 0\nEliminated: C\n\
+			Round 2:\nA: 2\nB: 3\nExhausted: 0\nWinner: B"
+				.to_string(),
+			handle_line("scores", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn vote_approval() {
+		let lexicon = Lexicon::french();
+		let scoreboard = Scoreboard::new(vec![
+			Candidate("MacOS".to_string()),
+			Candidate("Linux".to_string()),
+		]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::Approval);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"moi a approuve Linux, MacOS. (ignores: Windows)".to_string(),
+			strip_receipt(
+				handle_line("voter moi MacOS Windows Linux", controller, &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+	}
+
+	#[tokio::test]
+	async fn show_condorcet_winner() {
+		let lexicon = Lexicon::english();
+		let scoreboard =
+			Scoreboard::new(vec![Candidate("A".to_string()), Candidate("B".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::Schulze);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		for line in ["vote x A B", "vote y B A", "vote z A"] {
+			handle_line(line, controller.clone(), &lexicon)
+				.await
+				.unwrap();
+		}
+
+		assert_eq!(
+			"Scores:\nA: 2\nB: 1\nBlank: 0\nInvalid: 0\n\
+			Pairwise preferences:\nA: B 2\nB: A 1\nCondorcet winner: A"
+				.to_string(),
+			handle_line("scores", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn show_archived_round() {
+		let lexicon = Lexicon::french();
+		let scoreboard = Scoreboard::new(vec![
+			Candidate("A".to_string()),
+			Candidate("B".to_string()),
+			Candidate("C".to_string()),
+		]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_mode(ElectionMode::TwoRound);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		for line in [
+			"voter x A",
+			"voter y B",
+			"voter z C",
+			"voter w A",
+			"voter v B",
+		] {
+			handle_line(line, controller.clone(), &lexicon)
+				.await
+				.unwrap();
+		}
+
+		assert_eq!(
+			"Second tour entre A / B".to_string(),
+			handle_line("cloturer_tour", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"Tour 1:\nVoici les scores:\nA: 2\nB: 2\nC: 1\nBlanc: 0\nNull: 0".to_string(),
+			handle_line("tour 1", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn vote_before_opening() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_state(ElectionState::NotOpen);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"me cannot vote, the election is not open.".to_string(),
+			handle_line("vote me MacOS", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"The election is open.".to_string(),
+			handle_line("open", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"me has voted for MacOS.".to_string(),
+			strip_receipt(
+				handle_line("vote me MacOS", controller, &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+	}
+
+	#[tokio::test]
+	async fn show_turnout() {
+		let lexicon = Lexicon::french();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+		let roll = ElectoralRoll(
+			["moi", "toi", "lui"]
+				.into_iter()
+				.map(|voter| Voter(voter.to_string()))
+				.collect(),
+		);
+
+		let voting_machine =
+			VotingMachine::new(AttendenceSheet::default(), scoreboard).with_roll(roll);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"personne n'est pas inscrit sur la liste electorale.".to_string(),
+			handle_line("voter personne MacOS", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		handle_line("voter moi MacOS", controller.clone(), &lexicon)
+			.await
+			.unwrap();
+		assert_eq!(
+			"Voici les scores:\nMacOS: 1\nBlanc: 0\nNull: 0\nParticipation: 1/3".to_string(),
+			handle_line("scores", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn proxy_vote() {
+		let lexicon = Lexicon::french();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"moi a donne procuration a toi.".to_string(),
+			handle_line("procuration moi toi", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"moi a voter pour MacOS.".to_string(),
+			strip_receipt(
+				handle_line(
+					"voter_procuration toi moi MacOS",
+					controller.clone(),
+					&lexicon
+				)
+				.await
+				.unwrap(),
+				&lexicon,
+			)
+		);
+		assert_eq!(
+			"Voici les votants:\n- moi (vote par toi)\n".to_string(),
+			handle_line("votants", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn vote_on_question() {
+		let lexicon = Lexicon::english();
+		let main = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![Candidate("MacOS".to_string())]),
+		);
+		let referendum = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![
+				Candidate("yes".to_string()),
+				Candidate("no".to_string()),
+			]),
+		);
+		let session =
+			VotingSession::new(main).with_question(QuestionId("budget".to_string()), referendum);
+
+		let memory = MemoryStore::new(session).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"me has voted for yes.".to_string(),
+			strip_receipt(
+				handle_line("question budget vote me yes", controller.clone(), &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+		assert_eq!(
+			"me has voted for MacOS.".to_string(),
+			strip_receipt(
+				handle_line("vote me MacOS", controller.clone(), &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+		assert_eq!(
+			"Unknown question.".to_string(),
+			handle_line("question tax vote me yes", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"Questions:\n- budget: no, yes\n- main: MacOS\n".to_string(),
+			handle_line("questions", controller, &lexicon)
+				.await
+				.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn show_results() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![
+			Candidate("MacOS".to_string()),
+			Candidate("Linux".to_string()),
+		]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		for line in ["vote a Linux", "vote b Linux", "vote c MacOS", "vote d"] {
+			handle_line(line, controller.clone(), &lexicon)
+				.await
+				.unwrap();
+		}
+
+		assert_eq!(
+			"Results:\n\
+			1. Linux: 2 (66.67% of valid votes, 50.00% of ballots cast)\n\
+			2. MacOS: 1 (33.33% of valid votes, 25.00% of ballots cast)\n\
+			Absolute majority: Linux"
+				.to_string(),
+			handle_line("results", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn change_vote() {
+		let lexicon = Lexicon::french();
+		let scoreboard = Scoreboard::new(vec![
+			Candidate("MacOS".to_string()),
+			Candidate("Linux".to_string()),
+		]);
+
+		let voting_machine =
+			VotingMachine::new(AttendenceSheet::default(), scoreboard).with_revotes(true);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		handle_line("voter moi MacOS", controller.clone(), &lexicon)
+			.await
+			.unwrap();
+		assert_eq!(
+			"Vote modifie : moi a voter pour Linux.".to_string(),
+			strip_receipt(
+				handle_line("voter moi Linux", controller, &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+	}
+
+	#[tokio::test]
+	async fn secret_vote() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine =
+			VotingMachine::new(AttendenceSheet::default(), scoreboard).with_secret(true);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"me has cast a ballot.".to_string(),
+			strip_receipt(
+				handle_line("vote me MacOS", controller, &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+	}
+
+	#[tokio::test]
+	async fn manage_candidates() {
+		let lexicon = Lexicon::french();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_state(ElectionState::NotOpen);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"Linux a ete ajoute.".to_string(),
+			handle_line("ajouter Linux", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"Linux a ete renomme en Debian.".to_string(),
+			handle_line("renommer Linux Debian", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		handle_line("ouvrir", controller.clone(), &lexicon)
+			.await
+			.unwrap();
+		assert_eq!(
+			"Politique inconnue.".to_string(),
+			handle_line("retirer MacOS effacer", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"MacOS a ete retire (annuler).".to_string(),
+			handle_line("retirer MacOS annuler", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"Voici le journal:\n\
+			1. Linux a ete ajoute.\n\
+			2. Linux a ete renomme en Debian.\n\
+			3. MacOS a ete retire (annuler).\n"
+				.to_string(),
+			handle_line("journal", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn write_in_vote() {
+		let lexicon = Lexicon::french();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_write_ins(true)
+			.with_identity(IdentityPolicy {
+				case: CasePolicy::Insensitive,
+				accents: AccentPolicy::Kept,
+			});
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		assert_eq!(
+			"moi a voter pour linux.".to_string(),
+			strip_receipt(
+				handle_line("voter moi Linux", controller.clone(), &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+		assert_eq!(
+			"toi a voter pour MacOS.".to_string(),
+			strip_receipt(
+				handle_line("voter toi macos", controller, &lexicon)
+					.await
+					.unwrap(),
+				&lexicon,
+			)
+		);
+	}
+
+	#[tokio::test]
+	async fn show_receipts() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		let message = handle_line("vote me MacOS", controller.clone(), &lexicon)
+			.await
+			.unwrap();
+		let (_, receipt) = message.rsplit_once(' ').unwrap();
+		assert_eq!(
+			format!("Bulletin board:\n{receipt}\n"),
+			handle_line("receipts", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn verify_vote_log() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		handle_line("vote me MacOS", controller.clone(), &lexicon)
+			.await
+			.unwrap();
+		assert_eq!(
+			"me has already voted.".to_string(),
+			handle_line("vote me MacOS", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert_eq!(
+			"The vote log is intact, entries: 2".to_string(),
+			handle_line("verify", controller, &lexicon).await.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn history_and_past_results() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![Candidate("MacOS".to_string())]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_state(ElectionState::NotOpen);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		for line in ["add Linux", "open", "open", "vote me Linux"] {
+			handle_line(line, controller.clone(), &lexicon)
+				.await
+				.unwrap();
+		}
+
+		let history = handle_line("history", controller.clone(), &lexicon)
+			.await
+			.unwrap();
+		let lines: Vec<&str> = history.lines().collect();
+		assert_eq!(
+			vec![
+				"History:",
+				"1. Linux has been added.",
+				"2. The election is open."
+			],
+			lines[..3]
+		);
+		assert!(lines[3].starts_with("3. "));
+		assert!(lines[3].ends_with(" UTC ballot from me (stdio)"));
+		assert_eq!(4, lines.len());
+
+		assert_eq!(
+			"Results:\n\
+			1. Linux: 0 (0.00% of valid votes, 0.00% of ballots cast)\n\
+			1. MacOS: 0 (0.00% of valid votes, 0.00% of ballots cast)\n\
+			No valid vote."
+				.to_string(),
+			handle_line("results 2", controller.clone(), &lexicon)
+				.await
+				.unwrap()
+		);
+		assert!(handle_line("results", controller, &lexicon)
+			.await
+			.unwrap()
+			.starts_with("Results:\n1. Linux: 1 "));
+	}
+
+	#[tokio::test]
+	async fn secret_past_results() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![
+			Candidate("MacOS".to_string()),
+			Candidate("Linux".to_string()),
+		]);
+
+		let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+			.with_state(ElectionState::NotOpen)
+			.with_secret(true);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		for line in ["open", "vote me MacOS", "vote you Linux"] {
+			handle_line(line, controller.clone(), &lexicon)
+				.await
+				.unwrap();
+		}
+		for line in ["results 1", "results 2"] {
+			assert_eq!(
+				"The results of a secret ballot are only given after its last event.".to_string(),
+				handle_line(line, controller.clone(), &lexicon)
+					.await
+					.unwrap()
+			);
+		}
+		assert_eq!(
+			handle_line("results", controller.clone(), &lexicon)
+				.await
+				.unwrap(),
+			handle_line("results 3", controller, &lexicon)
+				.await
+				.unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn recount_ballots() {
+		let lexicon = Lexicon::english();
+		let scoreboard = Scoreboard::new(vec![
+			Candidate("MacOS".to_string()),
+			Candidate("Linux".to_string()),
+		]);
+
+		let voting_machine =
+			VotingMachine::new(AttendenceSheet::default(), scoreboard).with_revotes(true);
+
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+
+		for line in [
+			"vote me MacOS",
+			"vote you MacOS",
+			"vote them",
+			"vote me Linux",
+			"withdraw MacOS invalidate",
+			"rename Linux Tux",
+		] {
+			handle_line(line, controller.clone(), &lexicon)
+				.await
+				.unwrap();
+		}
+		assert_eq!(
+			"3 ballots recounted, the scores match.".to_string(),
+			handle_line("recount", controller, &lexicon).await.unwrap()
+		);
+
+		let recount = Recount {
+			ballots: 3,
+			discrepancies: vec![
+				Discrepancy {
+					line: TallyLine::Candidate(Candidate("Tux".to_string())),
+					stored: Score(2),
+					recounted: Score(1),
+				},
+				Discrepancy {
+					line: TallyLine::Blank,
+					stored: Score(0),
+					recounted: Score(1),
+				},
+			],
+		};
+		assert_eq!(
+			"3 ballots recounted, the scores differ:\nTux: 2 stored, 1 recounted\nBlank: 0 stored, 1 recounted",
+			show_recount(&recount, &lexicon)
+		);
+
+		let recount = Recount {
+			ballots: 0,
+			discrepancies: vec![Discrepancy {
+				line: TallyLine::Candidate(Candidate("MacOS".to_string())),
+				stored: Score(4),
+				recounted: Score(0),
+			}],
+		};
+		assert_eq!(
+			"No ballots stored, the scores cannot be recounted.",
+			show_recount(&recount, &lexicon)
+		);
+	}
 }
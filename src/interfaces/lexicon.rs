@@ -1,14 +1,19 @@
 #[derive(PartialEq, Eq, Clone)]
 pub struct Lexicon {
-	pub Voters: &'static str,
-	pub Scores: &'static str,
+	pub voters_heading: &'static str,
+	pub scores_heading: &'static str,
 	pub candidate: &'static str,
 	pub voter: &'static str,
 	pub vote_machine: &'static str,
 	pub urn: &'static str,
 	pub blank_vote: &'static str,
 	pub has_already_voted: &'static str,
+	pub vote_changed: &'static str,
+	pub ballot_cast: &'static str,
 	pub accepted_vote: &'static str,
+	pub accepted_ranked_vote: &'static str,
+	pub accepted_approval_vote: &'static str,
+	pub ignored_candidates: &'static str,
 	pub invalid_vote: &'static str,
 	pub vote: &'static str,
 	pub blank: &'static str,
@@ -20,4 +25,100 @@ pub struct Lexicon {
 	pub invalid_command: &'static str,
 	pub help: &'static str,
 	pub candidate_missing: &'static str,
+	pub round: &'static str,
+	pub eliminated: &'static str,
+	pub exhausted: &'static str,
+	pub winner: &'static str,
+	pub no_winner: &'static str,
+	pub last_place_tie: &'static str,
+	pub name_order: &'static str,
+	pub pairwise_title: &'static str,
+	pub condorcet_winner: &'static str,
+	pub schulze_ranking: &'static str,
+	pub quota: &'static str,
+	pub elected: &'static str,
+	pub transfer: &'static str,
+	pub close_round: &'static str,
+	pub round_command: &'static str,
+	pub second_round: &'static str,
+	pub tie: &'static str,
+	pub tie_break: &'static str,
+	pub runoff: &'static str,
+	pub not_two_round: &'static str,
+	pub unknown_round: &'static str,
+	pub election_not_open: &'static str,
+	pub election_closed: &'static str,
+	pub deadline_passed: &'static str,
+	pub open: &'static str,
+	pub close: &'static str,
+	pub state: &'static str,
+	pub state_not_open: &'static str,
+	pub state_open: &'static str,
+	pub state_closed: &'static str,
+	pub invalid_transition: &'static str,
+	pub not_registered: &'static str,
+	pub turnout: &'static str,
+	pub proxy: &'static str,
+	pub proxy_vote: &'static str,
+	pub proxy_holder: &'static str,
+	pub proxy_given: &'static str,
+	pub proxy_same_voter: &'static str,
+	pub proxy_already_given: &'static str,
+	pub too_many_proxies: &'static str,
+	pub not_proxy_holder: &'static str,
+	pub cast_by: &'static str,
+	pub proxy_missing: &'static str,
+	pub results: &'static str,
+	pub results_title: &'static str,
+	pub of_valid: &'static str,
+	pub of_cast: &'static str,
+	pub absolute_majority: &'static str,
+	pub relative_majority: &'static str,
+	pub no_valid_vote: &'static str,
+	pub add_command: &'static str,
+	pub withdraw_command: &'static str,
+	pub rename_command: &'static str,
+	pub audit_command: &'static str,
+	pub audit_title: &'static str,
+	pub candidate_added: &'static str,
+	pub candidate_withdrawn: &'static str,
+	pub admin_title: &'static str,
+	pub withdrawn: &'static str,
+	pub voting_page: &'static str,
+	pub candidate_renamed: &'static str,
+	pub candidate_exists: &'static str,
+	pub unknown_candidate: &'static str,
+	pub candidate_locked: &'static str,
+	pub policy_keep: &'static str,
+	pub policy_invalidate: &'static str,
+	pub policy_redistribute: &'static str,
+	pub policy_unavailable: &'static str,
+	pub unknown_policy: &'static str,
+	pub withdrawal_policy: &'static str,
+	pub new_name: &'static str,
+	pub question: &'static str,
+	pub questions: &'static str,
+	pub questions_title: &'static str,
+	pub unknown_question: &'static str,
+	pub receipt: &'static str,
+	pub receipts_command: &'static str,
+	pub bulletin_board: &'static str,
+	pub verify_command: &'static str,
+	pub vote_log_intact: &'static str,
+	pub vote_log_broken: &'static str,
+	pub history_broken: &'static str,
+	pub history_command: &'static str,
+	pub history_title: &'static str,
+	pub secret_past_results: &'static str,
+	pub ballot_from: &'static str,
+	pub round_closed: &'static str,
+	pub opens_in: &'static str,
+	pub closes_in: &'static str,
+	pub voting_ended: &'static str,
+	pub recount_command: &'static str,
+	pub recount_matches: &'static str,
+	pub recount_differs: &'static str,
+	pub stored_score: &'static str,
+	pub recounted_score: &'static str,
+	pub no_ballots_stored: &'static str,
 }
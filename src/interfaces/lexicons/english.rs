@@ -4,25 +4,126 @@ impl Lexicon {
 	pub const fn english() -> Self {
 		Self {
 			blank_vote: "has voted blank.",
+			vote_changed: "Vote changed:",
+			ballot_cast: "has cast a ballot.",
 			has_already_voted: "has already voted.",
 			accepted_vote: "has voted for",
+			accepted_ranked_vote: "has ranked",
+			accepted_approval_vote: "has approved",
+			ignored_candidates: "ignored",
 			invalid_vote: "has voted null.",
 			voters: "voters",
 			vote: "vote",
 			scores: "scores",
-			invalid_command: "Invalid command\n",
+			invalid_command: "Invalid command",
 			scores_title: "Scores:\n",
 			voters_title: "Voters:\n",
 			blank: "Blank",
 			invalid: "Invalid",
-			help: "Help :\n - vote <name> [candidate]\n - scores\n - voters",
+			help: "Help :\n - vote <name> [candidate...]\n - scores\n - results [event]\n - voters\n - open\n - close\n - state\n - close_round\n - round [number]\n - proxy <voter> <holder>\n - proxy_vote <holder> <voter> [candidate...]\n - add <candidate>\n - withdraw <candidate> [keep|invalidate|redistribute]\n - rename <old> <new>\n - audit\n - receipts\n - verify\n - recount\n - history\n - questions\n - question <id> <command>",
 			candidate_missing: "Voter missing.",
+			round: "Round",
+			eliminated: "Eliminated",
+			exhausted: "Exhausted",
+			winner: "Winner",
+			no_winner: "No winner",
+			last_place_tie: "Tie for last place",
+			name_order: "Name order",
+			pairwise_title: "Pairwise preferences",
+			condorcet_winner: "Condorcet winner",
+			schulze_ranking: "Schulze ranking",
+			quota: "Quota",
+			elected: "Elected",
+			transfer: "Transfer from",
+			close_round: "close_round",
+			round_command: "round",
+			second_round: "Second round between",
+			tie: "Tie between",
+			tie_break: "Tie-break",
+			runoff: "Runoff between",
+			not_two_round: "This election has a single round.",
+			unknown_round: "Unknown round.",
+			election_not_open: "cannot vote, the election is not open.",
+			election_closed: "cannot vote, the election is closed.",
+			deadline_passed: "cannot vote anymore, the closing time has passed.",
+			open: "open",
+			close: "close",
+			state: "state",
+			state_not_open: "The election is not open yet.",
+			state_open: "The election is open.",
+			state_closed: "The election is closed.",
+			invalid_transition: "Invalid transition.",
+			not_registered: "is not on the electoral roll.",
+			turnout: "Turnout",
+			proxy: "proxy",
+			proxy_vote: "proxy_vote",
+			proxy_holder: "Proxy holder",
+			proxy_given: "has given a proxy to",
+			proxy_same_voter: "cannot give a proxy to themselves.",
+			proxy_already_given: "has already given a proxy.",
+			too_many_proxies: "already holds the maximum number of proxies.",
+			not_proxy_holder: "holds no proxy from",
+			cast_by: "cast by",
+			proxy_missing: "Voter or proxy holder missing.",
+			results: "results",
+			results_title: "Results:\n",
+			of_valid: "of valid votes",
+			of_cast: "of ballots cast",
+			absolute_majority: "Absolute majority",
+			relative_majority: "Relative majority",
+			no_valid_vote: "No valid vote.",
+			add_command: "add",
+			withdraw_command: "withdraw",
+			rename_command: "rename",
+			audit_command: "audit",
+			audit_title: "Audit log:\n",
+			candidate_added: "has been added.",
+			admin_title: "Administration",
+			withdrawn: "withdrawn",
+			voting_page: "Back to the vote",
+			candidate_withdrawn: "has been withdrawn",
+			candidate_renamed: "has been renamed to",
+			candidate_exists: "is already a candidate.",
+			unknown_candidate: "is not a candidate.",
+			candidate_locked: "Not possible now.",
+			policy_keep: "keep",
+			policy_invalidate: "invalidate",
+			policy_redistribute: "redistribute",
+			policy_unavailable: "only applies to ranked ballots.",
+			unknown_policy: "Unknown policy.",
+			withdrawal_policy: "Existing ballots",
+			new_name: "New name",
+			question: "question",
+			questions: "questions",
+			questions_title: "Questions:\n",
+			unknown_question: "Unknown question.",
+			receipt: "Receipt:",
+			receipts_command: "receipts",
+			bulletin_board: "Bulletin board:\n",
+			verify_command: "verify",
+			vote_log_intact: "The vote log is intact, entries:",
+			vote_log_broken: "The vote log is broken at entry",
+			history_broken: "The recorded history is broken at event",
+			history_command: "history",
+			history_title: "History:\n",
+			secret_past_results: "The results of a secret ballot are only given after its last event.",
+			ballot_from: "ballot from",
+			round_closed: "Round closed.",
+			opens_in: "Opens in",
+			closes_in: "Closes in",
+			voting_ended: "The closing time has passed.",
+			recount_command: "recount",
+			recount_matches: "ballots recounted, the scores match.",
+			recount_differs: "ballots recounted, the scores differ:",
+			stored_score: "stored",
+			recounted_score: "recounted",
+			no_ballots_stored: "No ballots stored, the scores cannot be recounted.",
 			vote_machine: "Voting Machine",
 			urn: "Urn",
 			voter: "Voter",
 			candidate: "Candidate",
-			Scores: "Scores",
-			Voters: "Voters",
+			scores_heading: "Scores",
+			voters_heading: "Voters",
 		}
 	}
 }
@@ -5,24 +5,125 @@ impl Lexicon {
 		Self {
 			blank_vote: "a voter blanc.",
 			has_already_voted: "a deja voter.",
+			vote_changed: "Vote modifie :",
+			ballot_cast: "a depose son bulletin.",
 			accepted_vote: "a voter pour",
+			accepted_ranked_vote: "a classe",
+			accepted_approval_vote: "a approuve",
+			ignored_candidates: "ignores",
 			invalid_vote: "a voter null.",
 			voters: "votants",
 			vote: "voter",
 			scores: "scores",
 			blank: "Blanc",
 			invalid: "Null",
-			invalid_command: "Commande non valide\n",
+			invalid_command: "Commande non valide",
 			scores_title: "Voici les scores:\n",
 			voters_title: "Voici les votants:\n",
-			help: "Aide :\n - voter <nom> [candidat]\n - scores\n - votants",
+			help: "Aide :\n - voter <nom> [candidat...]\n - scores\n - resultats [evenement]\n - votants\n - ouvrir\n - fermer\n - etat\n - cloturer_tour\n - tour [numero]\n - procuration <mandant> <mandataire>\n - voter_procuration <mandataire> <mandant> [candidat...]\n - ajouter <candidat>\n - retirer <candidat> [garder|annuler|reporter]\n - renommer <ancien> <nouveau>\n - journal\n - recus\n - verifier\n - recompter\n - historique\n - questions\n - question <id> <commande>",
 			candidate_missing: "Il manque un votant.",
+			round: "Tour",
+			eliminated: "Elimine",
+			exhausted: "Epuises",
+			winner: "Vainqueur",
+			no_winner: "Aucun vainqueur",
+			last_place_tie: "Egalite pour la derniere place",
+			name_order: "Ordre alphabetique",
+			pairwise_title: "Duels",
+			condorcet_winner: "Vainqueur de Condorcet",
+			schulze_ranking: "Classement de Schulze",
+			quota: "Quotient",
+			elected: "Elus",
+			transfer: "Transfert de",
+			close_round: "cloturer_tour",
+			round_command: "tour",
+			second_round: "Second tour entre",
+			tie: "Egalite entre",
+			tie_break: "Departage",
+			runoff: "Nouveau tour entre",
+			not_two_round: "Cette election n'a qu'un tour.",
+			unknown_round: "Tour inconnu.",
+			election_not_open: "ne peut pas voter, le vote n'est pas ouvert.",
+			election_closed: "ne peut pas voter, le vote est clos.",
+			deadline_passed: "ne peut plus voter, l'heure de cloture est passee.",
+			open: "ouvrir",
+			close: "fermer",
+			state: "etat",
+			state_not_open: "Le vote n'est pas encore ouvert.",
+			state_open: "Le vote est ouvert.",
+			state_closed: "Le vote est clos.",
+			invalid_transition: "Transition impossible.",
+			not_registered: "n'est pas inscrit sur la liste electorale.",
+			turnout: "Participation",
+			proxy: "procuration",
+			proxy_vote: "voter_procuration",
+			proxy_holder: "Mandataire",
+			proxy_given: "a donne procuration a",
+			proxy_same_voter: "ne peut pas se donner procuration.",
+			proxy_already_given: "a deja donne procuration.",
+			too_many_proxies: "a deja le nombre maximal de procurations.",
+			not_proxy_holder: "n'a pas procuration de",
+			cast_by: "vote par",
+			proxy_missing: "Il manque le mandant ou le mandataire.",
+			results: "resultats",
+			results_title: "Voici les resultats:\n",
+			of_valid: "des exprimes",
+			of_cast: "des votants",
+			absolute_majority: "Majorite absolue",
+			relative_majority: "Majorite relative",
+			no_valid_vote: "Aucun suffrage exprime.",
+			add_command: "ajouter",
+			withdraw_command: "retirer",
+			rename_command: "renommer",
+			audit_command: "journal",
+			audit_title: "Voici le journal:\n",
+			candidate_added: "a ete ajoute.",
+			admin_title: "Administration",
+			withdrawn: "retire",
+			voting_page: "Retour au vote",
+			candidate_withdrawn: "a ete retire",
+			candidate_renamed: "a ete renomme en",
+			candidate_exists: "est deja candidat.",
+			unknown_candidate: "n'est pas candidat.",
+			candidate_locked: "Operation impossible.",
+			policy_keep: "garder",
+			policy_invalidate: "annuler",
+			policy_redistribute: "reporter",
+			policy_unavailable: "ne s'applique qu'aux votes par classement.",
+			unknown_policy: "Politique inconnue.",
+			withdrawal_policy: "Bulletins existants",
+			new_name: "Nouveau nom",
+			question: "question",
+			questions: "questions",
+			questions_title: "Voici les questions:\n",
+			unknown_question: "Question inconnue.",
+			receipt: "Recu :",
+			receipts_command: "recus",
+			bulletin_board: "Voici les recus:\n",
+			verify_command: "verifier",
+			vote_log_intact: "Le journal des votes est intact, entrees :",
+			vote_log_broken: "Le journal des votes est rompu a l'entree",
+			history_broken: "L'historique enregistre est rompu a l'evenement",
+			history_command: "historique",
+			history_title: "Voici l'historique:\n",
+			secret_past_results: "Les resultats d'un scrutin secret ne sont donnes qu'apres son dernier evenement.",
+			ballot_from: "bulletin de",
+			round_closed: "Tour cloture.",
+			opens_in: "Ouverture dans",
+			closes_in: "Cloture dans",
+			voting_ended: "L'heure de cloture est passee.",
+			recount_command: "recompter",
+			recount_matches: "bulletins recomptes, les scores concordent.",
+			recount_differs: "bulletins recomptes, les scores different :",
+			stored_score: "enregistres",
+			recounted_score: "recomptes",
+			no_ballots_stored: "Aucun bulletin enregistre, les scores ne peuvent pas etre recomptes.",
 			vote_machine: "Machine de vote",
 			urn: "Urne",
 			voter: "Votant",
 			candidate: "Candidat",
-			Scores: "Scores",
-			Voters: "Votants",
+			scores_heading: "Scores",
+			voters_heading: "Votants",
 		}
 	}
 }
@@ -1,19 +1,221 @@
 use lexicon::Lexicon;
 
-use crate::domain::vote_outcome::VoteOutcome;
+use crate::domain::{
+	candidate_change::{AuditEntry, CandidateOutcome, WithdrawalPolicy},
+	election_state::{ElectionState, TransitionOutcome},
+	instant_runoff::{LastPlaceRule, LastPlaceTie},
+	proxy::ProxyOutcome,
+	receipt::ReceiptedVote,
+	results::Majority,
+	tie_break::{TieBreak, TieResolution},
+	vote_log::ChainVerification,
+	vote_outcome::VoteOutcome,
+};
 
 pub mod cli_interfaces;
 pub mod lexicon;
 pub mod lexicons;
 pub mod web_interfaces;
 
+fn show_receipted_vote(vote: ReceiptedVote, lexicon: &Lexicon) -> String {
+	let outcome = show_vote_outcome(vote.outcome, lexicon);
+	match vote.receipt {
+		Some(receipt) => format!("{outcome}\n{} {receipt}", lexicon.receipt),
+		None => outcome,
+	}
+}
+
 fn show_vote_outcome(outcome: VoteOutcome, lexicon: &Lexicon) -> String {
 	match outcome {
 		VoteOutcome::AcceptedVote(voter, candidate) => {
 			format!("{voter} {} {candidate}.", lexicon.accepted_vote)
 		}
+		VoteOutcome::AcceptedRankedVote(voter, ranking) => {
+			let ranking: Vec<String> = ranking.iter().map(ToString::to_string).collect();
+			format!(
+				"{voter} {} {}.",
+				lexicon.accepted_ranked_vote,
+				ranking.join(" > ")
+			)
+		}
+		VoteOutcome::AcceptedApprovalVote(voter, approved, unknown) => {
+			let approved: Vec<String> = approved.iter().map(ToString::to_string).collect();
+			let mut res = format!(
+				"{voter} {} {}.",
+				lexicon.accepted_approval_vote,
+				approved.join(", ")
+			);
+			if !unknown.is_empty() {
+				let unknown: Vec<String> = unknown.iter().map(ToString::to_string).collect();
+				res += &format!(" ({}: {})", lexicon.ignored_candidates, unknown.join(", "));
+			}
+			res
+		}
 		VoteOutcome::BlankVote(voter) => format!("{voter} {}", lexicon.blank_vote),
 		VoteOutcome::InvalidVote(voter) => format!("{voter} {}", lexicon.invalid_vote),
+		VoteOutcome::VoteChanged(outcome) => {
+			format!(
+				"{} {}",
+				lexicon.vote_changed,
+				show_vote_outcome(*outcome, lexicon)
+			)
+		}
+		VoteOutcome::BallotCast(voter) => format!("{voter} {}", lexicon.ballot_cast),
 		VoteOutcome::HasAlreadyVoted(voter) => format!("{voter} {}", lexicon.has_already_voted),
+		VoteOutcome::NotProxyHolder(holder, voter) => {
+			format!("{holder} {} {voter}.", lexicon.not_proxy_holder)
+		}
+		VoteOutcome::NotRegistered(voter) => format!("{voter} {}", lexicon.not_registered),
+		VoteOutcome::ElectionNotOpen(voter) => format!("{voter} {}", lexicon.election_not_open),
+		VoteOutcome::ElectionClosed(voter) => format!("{voter} {}", lexicon.election_closed),
+		VoteOutcome::DeadlinePassed(voter) => format!("{voter} {}", lexicon.deadline_passed),
+	}
+}
+
+fn show_proxy_outcome(outcome: ProxyOutcome, lexicon: &Lexicon) -> String {
+	match outcome {
+		ProxyOutcome::Registered(voter, holder) => {
+			format!("{voter} {} {holder}.", lexicon.proxy_given)
+		}
+		ProxyOutcome::SameVoter(voter) => format!("{voter} {}", lexicon.proxy_same_voter),
+		ProxyOutcome::NotRegistered(voter) => format!("{voter} {}", lexicon.not_registered),
+		ProxyOutcome::HasAlreadyVoted(voter) => format!("{voter} {}", lexicon.has_already_voted),
+		ProxyOutcome::AlreadyGiven(voter) => format!("{voter} {}", lexicon.proxy_already_given),
+		ProxyOutcome::TooManyProxies(holder) => format!("{holder} {}", lexicon.too_many_proxies),
+		ProxyOutcome::ElectionClosed => lexicon.state_closed.to_string(),
+	}
+}
+
+const fn show_election_state(state: ElectionState, lexicon: &Lexicon) -> &'static str {
+	match state {
+		ElectionState::NotOpen => lexicon.state_not_open,
+		ElectionState::Open => lexicon.state_open,
+		ElectionState::Closed => lexicon.state_closed,
+	}
+}
+
+fn show_majority(majority: &Majority, lexicon: &Lexicon) -> String {
+	match majority {
+		Majority::Absolute(candidate) => format!("{}: {candidate}", lexicon.absolute_majority),
+		Majority::Relative(candidate) => format!("{}: {candidate}", lexicon.relative_majority),
+		Majority::Tie(candidates) => {
+			let candidates: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+			format!("{} {}", lexicon.tie, candidates.join(" / "))
+		}
+		Majority::NoValidVote => lexicon.no_valid_vote.to_string(),
+	}
+}
+
+fn show_tie_break(tie_break: &TieBreak, lexicon: &Lexicon) -> String {
+	let tied: Vec<String> = tie_break.tied.iter().map(ToString::to_string).collect();
+	let resolution = match &tie_break.resolution {
+		TieResolution::Winner(winner) => format!("{}: {winner}", lexicon.winner),
+		TieResolution::Runoff(candidates) => {
+			let candidates: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+			format!("{} {}", lexicon.runoff, candidates.join(" / "))
+		}
+		TieResolution::Unresolved => lexicon.no_winner.to_string(),
+	};
+	format!("{} {}: {resolution}", lexicon.tie_break, tied.join(" / "))
+}
+
+fn show_last_place_tie(tie: &LastPlaceTie, lexicon: &Lexicon) -> String {
+	let tied: Vec<String> = tie.tied.iter().map(ToString::to_string).collect();
+	let settled_by = match tie.settled_by {
+		LastPlaceRule::EarlierRound(number) => format!("{} {number}", lexicon.round),
+		LastPlaceRule::NameOrder => lexicon.name_order.to_string(),
+	};
+	format!(
+		"{} {}: {settled_by}",
+		lexicon.last_place_tie,
+		tied.join(" / ")
+	)
+}
+
+const fn show_withdrawal_policy(policy: WithdrawalPolicy, lexicon: &Lexicon) -> &'static str {
+	match policy {
+		WithdrawalPolicy::Keep => lexicon.policy_keep,
+		WithdrawalPolicy::Invalidate => lexicon.policy_invalidate,
+		WithdrawalPolicy::Redistribute => lexicon.policy_redistribute,
+	}
+}
+
+fn show_candidate_outcome(outcome: CandidateOutcome, lexicon: &Lexicon) -> String {
+	match outcome {
+		CandidateOutcome::Added(candidate) => format!("{candidate} {}", lexicon.candidate_added),
+		CandidateOutcome::Withdrawn(candidate, policy) => format!(
+			"{candidate} {} ({}).",
+			lexicon.candidate_withdrawn,
+			show_withdrawal_policy(policy, lexicon)
+		),
+		CandidateOutcome::Renamed(candidate, new_name) => {
+			format!("{candidate} {} {new_name}.", lexicon.candidate_renamed)
+		}
+		CandidateOutcome::AlreadyExists(candidate) => {
+			format!("{candidate} {}", lexicon.candidate_exists)
+		}
+		CandidateOutcome::UnknownCandidate(candidate) => {
+			format!("{candidate} {}", lexicon.unknown_candidate)
+		}
+		CandidateOutcome::PolicyUnavailable(policy) => format!(
+			"{} {}",
+			show_withdrawal_policy(policy, lexicon),
+			lexicon.policy_unavailable
+		),
+		CandidateOutcome::InvalidState(state) => format!(
+			"{} {}",
+			lexicon.candidate_locked,
+			show_election_state(state, lexicon)
+		),
+	}
+}
+
+/// Shows the first broken link of the recorded history, or else how the vote
+/// log of the question holds.
+fn show_verification(
+	history: &ChainVerification,
+	vote_log: &ChainVerification,
+	lexicon: &Lexicon,
+) -> String {
+	match (history, vote_log) {
+		(ChainVerification::Broken(event), _) => format!("{} {event}.", lexicon.history_broken),
+		(ChainVerification::Intact(_), ChainVerification::Intact(entries)) => {
+			format!("{} {entries}", lexicon.vote_log_intact)
+		}
+		(ChainVerification::Intact(_), ChainVerification::Broken(position)) => {
+			format!("{} {position}.", lexicon.vote_log_broken)
+		}
+	}
+}
+
+fn show_audit_entry(entry: &AuditEntry, lexicon: &Lexicon) -> String {
+	match entry {
+		AuditEntry::CandidateAdded(candidate) => {
+			format!("{candidate} {}", lexicon.candidate_added)
+		}
+		AuditEntry::CandidateWithdrawn(candidate, policy) => format!(
+			"{candidate} {} ({}).",
+			lexicon.candidate_withdrawn,
+			show_withdrawal_policy(*policy, lexicon)
+		),
+		AuditEntry::CandidateRenamed(candidate, new_name) => {
+			format!("{candidate} {} {new_name}.", lexicon.candidate_renamed)
+		}
+	}
+}
+
+fn show_transition_outcome(outcome: TransitionOutcome, lexicon: &Lexicon) -> String {
+	match outcome {
+		TransitionOutcome::Opened => lexicon.state_open.to_string(),
+		TransitionOutcome::Closed => lexicon.state_closed.to_string(),
+		TransitionOutcome::Runoff(candidates) => {
+			let candidates: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+			format!("{} {}", lexicon.runoff, candidates.join(" / "))
+		}
+		TransitionOutcome::InvalidTransition(state) => format!(
+			"{} {}",
+			lexicon.invalid_transition,
+			show_election_state(state, lexicon)
+		),
 	}
 }
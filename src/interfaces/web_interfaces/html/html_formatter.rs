@@ -1,51 +1,410 @@
 use maud::{html, Markup};
 
-use crate::{domain::voting_machine::VotingMachine, interfaces::lexicon::Lexicon};
+use crate::{
+	domain::{
+		election_mode::ElectionMode,
+		generic_domains::{Candidate, QuestionId},
+		instant_runoff::InstantRunoff,
+		results::Results,
+		schedule::{Countdown, Schedule},
+		schulze::Schulze,
+		scoreboard::Scoreboard,
+		single_transferable_vote::SingleTransferableVote,
+		vote_log::Timestamp,
+		voting_machine::VotingMachine,
+		voting_session::VotingSession,
+	},
+	interfaces::{
+		lexicon::Lexicon, show_audit_entry, show_election_state, show_last_place_tie,
+		show_majority, show_tie_break,
+	},
+};
 
 use super::web_routes::WebRoutes;
 
-pub fn vote_form(routes: &WebRoutes, lexicon: &Lexicon) -> Markup {
+/// Shows a candidate with its profile, with a radio button when `selectable`.
+pub fn candidate_card(scoreboard: &Scoreboard, candidate: &Candidate, selectable: bool) -> Markup {
+	let profile = scoreboard.profiles.get(candidate);
+	html!(
+		label .candidate_card {
+			@if selectable {
+				input type="radio" name="candidate" value=(candidate);
+			}
+			@if let Some(image) = profile.and_then(|profile| profile.image.as_ref()) {
+				img .candidate_image src=(image) alt=(scoreboard.display_name(candidate));
+			}
+			h3 .candidate_name { (scoreboard.display_name(candidate)) }
+			@if let Some(party) = profile.and_then(|profile| profile.party.as_ref()) {
+				p .candidate_party { (party) }
+			}
+			@if let Some(statement) = profile.and_then(|profile| profile.statement.as_ref()) {
+				p .candidate_statement { (statement) }
+			}
+		}
+	)
+}
+
+pub fn vote_form(
+	routes: &WebRoutes,
+	lexicon: &Lexicon,
+	question: &QuestionId,
+	machine: &VotingMachine,
+) -> Markup {
+	let scoreboard = machine.get_scoreboard();
+	// A write-in has to be typed, so the cards are then only shown.
+	let single_choice = matches!(
+		machine.get_mode(),
+		ElectionMode::Plurality | ElectionMode::TwoRound
+	) && !machine.get_write_ins();
+	html!(
+		script src="https://unpkg.com/htmx.org@1.9.2" {}
+		h1 #title { (lexicon.vote_machine) }
+		h2 #urne { (lexicon.urn) " - " (question) }
+		form hx-post=(routes.scoped(question, routes.vote)) hx-target="#outcome" {
+			div #inputs {
+				div #votant {
+					p { (lexicon.voter) }
+					input #input_votant name="voter";
+				}
+				div #candidat {
+					p { (lexicon.candidate) }
+					div #candidate_cards {
+						@for candidate in scoreboard.scores.keys() {
+							@if !machine.get_withdrawn().contains(candidate) {
+								(candidate_card(scoreboard, candidate, single_choice))
+							}
+						}
+					}
+					@if single_choice {
+						label .candidate_card {
+							input type="radio" name="candidate" value="" checked;
+							h3 .candidate_name { (lexicon.blank) }
+						}
+					} @else {
+						input #input_candidat name="candidate";
+					}
+				}
+				div #mandataire {
+					p { (lexicon.proxy_holder) }
+					input #input_mandataire name="proxy";
+				}
+			}
+			button { (lexicon.vote) }
+		}
+		form #procuration hx-post=(routes.scoped(question, routes.proxy)) hx-target="#outcome" {
+			div #proxy_inputs {
+				div {
+					p { (lexicon.voter) }
+					input name="voter";
+				}
+				div {
+					p { (lexicon.proxy_holder) }
+					input name="holder";
+				}
+			}
+			button { (lexicon.proxy) }
+		}
+		button #verify hx-get=(routes.scoped(question, routes.verify)) hx-target="#outcome" { (lexicon.verify_command) }
+		p #outcome {}
+	)
+}
+
+/// Page opening and closing the election and changing its candidates, which
+/// voters are not shown.
+pub fn admin(
+	routes: &WebRoutes,
+	lexicon: &Lexicon,
+	question: &QuestionId,
+	machine: &VotingMachine,
+) -> Markup {
+	let withdrawn = machine.get_withdrawn();
 	html!(
 		script src="https://unpkg.com/htmx.org@1.9.2" {}
 		h1 #title { (lexicon.vote_machine) }
-		h2 #urne { (lexicon.urn) }
-		div #inputs {
-			div #votant {
-				p { (lexicon.voter) }
-				input #input_votant;
+		h2 #admin_title { (lexicon.admin_title) " - " (question) }
+		p #state { (show_election_state(machine.get_state(), lexicon)) }
+		ul #admin_candidates {
+			@for candidate in machine.get_scoreboard().scores.keys() {
+				li {
+					(candidate)
+					@if withdrawn.contains(candidate) {
+						" (" (lexicon.withdrawn) ")"
+					}
+				}
+			}
+		}
+		div #admin {
+			button hx-post=(routes.scoped(question, routes.open)) hx-target="#outcome" { (lexicon.open) }
+			button hx-post=(routes.scoped(question, routes.close)) hx-target="#outcome" { (lexicon.close) }
+			form #add_candidate hx-post=(routes.scoped(question, routes.add_candidate)) hx-target="#outcome" {
+				p { (lexicon.candidate) }
+				input name="candidate";
+				button { (lexicon.add_command) }
+			}
+			form #withdraw_candidate hx-post=(routes.scoped(question, routes.withdraw_candidate)) hx-target="#outcome" {
+				p { (lexicon.candidate) }
+				input name="candidate";
+				p { (lexicon.withdrawal_policy) }
+				select name="policy" {
+					option value="keep" { (lexicon.policy_keep) }
+					option value="invalidate" { (lexicon.policy_invalidate) }
+					option value="redistribute" { (lexicon.policy_redistribute) }
+				}
+				button { (lexicon.withdraw_command) }
 			}
-			div #candidat {
+			form #rename_candidate hx-post=(routes.scoped(question, routes.rename_candidate)) hx-target="#outcome" {
 				p { (lexicon.candidate) }
-				input #input_candidat;
+				input name="candidate";
+				p { (lexicon.new_name) }
+				input name="new_name";
+				button { (lexicon.rename_command) }
+			}
+		}
+		p #outcome {}
+		a href=(routes.scoped(question, routes.index)) { (lexicon.voting_page) }
+	)
+}
+
+pub fn results(lexicon: &Lexicon, results: &Results) -> Markup {
+	html!(
+		h2 #results_title { (lexicon.results_title) }
+		table #results {
+			@for result in &results.ranking {
+				tr {
+					td { (result.rank) }
+					td {
+						(result.candidate)
+						@if result.withdrawn {
+							" (" (lexicon.withdrawn) ")"
+						}
+					}
+					td { (result.score) }
+					td { (result.of_valid) " " (lexicon.of_valid) }
+					td { (result.of_cast) " " (lexicon.of_cast) }
+				}
+			}
+		}
+		p #majority { (show_majority(&results.majority, lexicon)) }
+	)
+}
+
+pub fn instant_runoff(lexicon: &Lexicon, runoff: &InstantRunoff) -> Markup {
+	html!(
+		div #rounds {
+			@for (number, round) in runoff.rounds.iter().enumerate() {
+				h3 { (lexicon.round) " " (number + 1) }
+				@for (candidate, score) in &round.scores {
+					p { (candidate) ": " (score) }
+				}
+				p { (lexicon.exhausted) ": " (round.exhausted) }
+				@if let Some(tie) = &round.last_place_tie {
+					p .last_place_tie { (show_last_place_tie(tie, lexicon)) }
+				}
+				@if let Some(candidate) = &round.eliminated {
+					p { (lexicon.eliminated) ": " (candidate) }
+				}
+			}
+			@match &runoff.winner {
+				Some(winner) => p #winner { (lexicon.winner) ": " (winner) },
+				None => p #winner { (lexicon.no_winner) },
 			}
 		}
-		button { (lexicon.vote) }
 	)
 }
 
-pub fn voting_machine(routes: &WebRoutes, lexicon: &Lexicon, machine: &VotingMachine) -> Markup {
+pub fn schulze(lexicon: &Lexicon, schulze: &Schulze) -> Markup {
 	html!(
-		h2 #scores_title { (lexicon.Scores) }
+		h3 #pairwise_title { (lexicon.pairwise_title) }
+		table #pairwise {
+			tr {
+				th {}
+				@for candidate in schulze.pairwise.keys() {
+					th { (candidate) }
+				}
+			}
+			@for (row, columns) in &schulze.pairwise {
+				tr {
+					th { (row) }
+					@for column in schulze.pairwise.keys() {
+						td {
+							@if let Some(score) = columns.get(column) {
+								(score)
+							}
+						}
+					}
+				}
+			}
+		}
+		@if let Some(winner) = &schulze.condorcet_winner {
+			p #winner { (lexicon.condorcet_winner) ": " (winner) }
+		} @else {
+			h3 #ranking_title { (lexicon.schulze_ranking) }
+			ol #ranking {
+				@for tier in &schulze.ranking {
+					li {
+						@for (position, candidate) in tier.iter().enumerate() {
+							@if position > 0 { ", " }
+							(candidate)
+						}
+					}
+				}
+			}
+		}
+	)
+}
+
+pub fn single_transferable_vote(lexicon: &Lexicon, stv: &SingleTransferableVote) -> Markup {
+	html!(
+		div #rounds {
+			p #quota { (lexicon.quota) ": " (stv.quota) }
+			@for (number, round) in stv.rounds.iter().enumerate() {
+				h3 { (lexicon.round) " " (number + 1) }
+				@for (candidate, votes) in &round.tallies {
+					p { (candidate) ": " (votes) }
+				}
+				p { (lexicon.exhausted) ": " (round.exhausted) }
+				@if let Some(tie) = &round.last_place_tie {
+					p .last_place_tie { (show_last_place_tie(tie, lexicon)) }
+				}
+				@for candidate in &round.elected {
+					p { (lexicon.elected) ": " (candidate) }
+				}
+				@for candidate in &round.eliminated {
+					p { (lexicon.eliminated) ": " (candidate) }
+				}
+				@for transfer in &round.transfers {
+					p { (lexicon.transfer) " " (transfer.from) ": " (transfer.votes) }
+				}
+			}
+			h3 #elected_title { (lexicon.elected) }
+			ul #elected {
+				@for candidate in &stv.elected {
+					li { (candidate) }
+				}
+			}
+		}
+	)
+}
+
+pub fn voting_machine(lexicon: &Lexicon, machine: &VotingMachine) -> Markup {
+	html!(
+		p #state { (show_election_state(machine.get_state(), lexicon)) }
+		h2 #scores_title { (lexicon.scores_heading) }
 		div #scores {
 			@for (candidate, score) in &machine.get_scoreboard().scores {
-				p { (candidate) (score) }
+				p { (machine.get_scoreboard().display_name(candidate)) ": " (score) }
+			}
+			p { (lexicon.blank) ": " (&machine.get_scoreboard().blank_score) }
+			p { (lexicon.invalid) ": " (&machine.get_scoreboard().invalid_score) }
+			@if let Some(turnout) = machine.turnout() {
+				p #turnout { (lexicon.turnout) ": " (turnout.voted) "/" (turnout.registered) }
+			}
+		}
+		(results(lexicon, &machine.results()))
+		@for tie_break in machine.get_tie_breaks() {
+			p .tie_break { (show_tie_break(tie_break, lexicon)) }
+		}
+		@match machine.get_mode() {
+			ElectionMode::InstantRunoff => (instant_runoff(lexicon, &machine.instant_runoff())),
+			ElectionMode::Schulze => (schulze(lexicon, &machine.schulze())),
+			ElectionMode::SingleTransferableVote { .. } => {
+				(single_transferable_vote(lexicon, &machine.single_transferable_vote()))
+			},
+			ElectionMode::Plurality | ElectionMode::TwoRound | ElectionMode::Approval => {},
+		}
+		@for (number, round) in machine.get_archived_rounds().iter().enumerate() {
+			h2 { (lexicon.round) " " (number + 1) }
+			div .archived_scores {
+				@for (candidate, score) in &round.scoreboard.scores {
+					p { (candidate) ": " (score) }
+				}
+				p { (lexicon.blank) ": " (round.scoreboard.blank_score) }
+				p { (lexicon.invalid) ": " (round.scoreboard.invalid_score) }
+			}
+		}
+		@if !machine.get_audit().is_empty() {
+			h2 #audit_title { (lexicon.audit_title) }
+			ol #audit {
+				@for entry in machine.get_audit() {
+					li { (show_audit_entry(entry, lexicon)) }
+				}
+			}
+		}
+		@if !machine.get_receipts().is_empty() {
+			h2 #bulletin_board_title { (lexicon.bulletin_board) }
+			ul #bulletin_board {
+				@for receipt in machine.bulletin_board() {
+					li { code { (receipt) } }
+				}
 			}
-			p { (&machine.get_scoreboard().blank_score) }
-			p { (&machine.get_scoreboard().invalid_score) }
 		}
+		h2 #voters_title { (lexicon.voters_heading) }
 		div #voters {
 			ul {
 				@for voter in &machine.get_voter().0 {
-					li { (voter) }
+					li {
+						(voter)
+						@if let Some(holder) = machine.get_voter().1.get(voter) {
+							" (" (lexicon.cast_by) " " (holder) ")"
+						}
+					}
 				}
 			}
 		}
 	)
 }
 
-pub fn index(routes: &WebRoutes, lexicon: &Lexicon, machine: &VotingMachine) -> Markup {
-	let form = vote_form(routes, lexicon);
-	let machine = voting_machine(routes, lexicon, machine);
+pub fn questions(routes: &WebRoutes, lexicon: &Lexicon, session: &VotingSession) -> Markup {
+	html!(
+		h2 #questions_title { (lexicon.questions_title) }
+		ul #questions {
+			@for question in session.get_questions().keys() {
+				li { a href=(routes.scoped(question, routes.index)) { (question) } }
+			}
+		}
+	)
+}
+
+/// Shows the time left before the scheduled opening or closing, fetched
+/// again every second.
+pub fn countdown(
+	routes: &WebRoutes,
+	lexicon: &Lexicon,
+	question: &QuestionId,
+	schedule: &Schedule,
+	now: Timestamp,
+) -> Markup {
+	let (label, seconds) = match schedule.countdown(now) {
+		Countdown::Opens(seconds) => (lexicon.opens_in, seconds),
+		Countdown::Closes(seconds) => (lexicon.closes_in, seconds),
+		Countdown::DeadlinePassed => return html!(p #countdown { (lexicon.voting_ended) }),
+		Countdown::Unscheduled => return html!(),
+	};
+	let remaining = format!(
+		"{}:{:02}:{:02}",
+		seconds / 3_600,
+		seconds % 3_600 / 60,
+		seconds % 60
+	);
+	html!(
+		p #countdown hx-get=(routes.scoped(question, routes.countdown)) hx-trigger="every 1s" hx-swap="outerHTML" {
+			(label) " " (remaining)
+		}
+	)
+}
+
+pub fn index(
+	routes: &WebRoutes,
+	lexicon: &Lexicon,
+	session: &VotingSession,
+	question: &QuestionId,
+	machine: &VotingMachine,
+	now: Timestamp,
+) -> Markup {
+	let form = vote_form(routes, lexicon, question, machine);
+	let countdown = countdown(routes, lexicon, question, machine.get_schedule(), now);
+	let machine = voting_machine(lexicon, machine);
+	let questions = questions(routes, lexicon, session);
 
-	html!((form)(machine))
+	html!((form)(countdown)(machine)(questions))
 }
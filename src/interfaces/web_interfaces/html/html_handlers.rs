@@ -1,43 +1,179 @@
-use axum::{extract::State, response::IntoResponse, Form};
+use axum::{
+	extract::{Path, State},
+	response::IntoResponse,
+	Form,
+};
 
 use crate::{
-	domain::vote_outcome,
+	domain::generic_domains::QuestionId,
 	interfaces::{
-		show_vote_outcome,
+		show_candidate_outcome, show_proxy_outcome, show_receipted_vote, show_transition_outcome,
+		show_verification,
 		web_interfaces::{AxumError, AxumState},
 	},
 	storage::Storage,
-	use_cases::VoteForm,
+	use_cases::{CandidateForm, ProxyForm, VoteForm, VotingController},
 };
 
-use super::html_formatter::{index, vote_form, voting_machine};
+use super::html_formatter::{admin, countdown, index, voting_machine};
+
+/// Picks the question named in the path, the main one on unscoped routes.
+fn question_controller<Store: Storage>(
+	app_state: &AxumState<Store>,
+	question: Option<Path<String>>,
+) -> (QuestionId, VotingController<Store>) {
+	let question = question.map_or_else(QuestionId::main, |Path(question)| QuestionId(question));
+	let controller = app_state.controller.on_question(question.clone());
+	(question, controller)
+}
 
 pub async fn get_index<Store: Storage>(
 	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
 ) -> Result<impl IntoResponse, AxumError> {
+	let (question, controller) = question_controller(&app_state, question);
 	Ok(index(
 		&app_state.routes,
 		&app_state.lexicon,
-		&app_state.controller.get_voting_machine().await?,
+		&controller.get_voting_session().await?,
+		&question,
+		&controller.get_voting_machine().await?,
+		controller.now(),
+	))
+}
+
+pub async fn get_admin<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (question, controller) = question_controller(&app_state, question);
+	Ok(admin(
+		&app_state.routes,
+		&app_state.lexicon,
+		&question,
+		&controller.get_voting_machine().await?,
+	))
+}
+
+pub async fn get_countdown<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (question, controller) = question_controller(&app_state, question);
+	Ok(countdown(
+		&app_state.routes,
+		&app_state.lexicon,
+		&question,
+		controller.get_voting_machine().await?.get_schedule(),
+		controller.now(),
 	))
 }
 
 pub async fn get_results<Store: Storage>(
 	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
 ) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
 	Ok(voting_machine(
-		&app_state.routes,
 		&app_state.lexicon,
-		&app_state.controller.get_voting_machine().await?,
+		&controller.get_voting_machine().await?,
 	))
 }
 
 pub async fn vote<Store: Storage>(
 	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
 	Form(vote_form): Form<VoteForm>,
 ) -> Result<impl IntoResponse, AxumError> {
-	Ok(show_vote_outcome(
-		app_state.controller.vote(vote_form).await?,
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_receipted_vote(
+		controller.vote(vote_form).await?,
+		&app_state.lexicon,
+	))
+}
+
+pub async fn open_election<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_transition_outcome(
+		controller.open_election().await?,
+		&app_state.lexicon,
+	))
+}
+
+pub async fn close_election<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_transition_outcome(
+		controller.close_election().await?,
+		&app_state.lexicon,
+	))
+}
+
+pub async fn register_proxy<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+	Form(proxy_form): Form<ProxyForm>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_proxy_outcome(
+		controller.register_proxy(proxy_form).await?,
+		&app_state.lexicon,
+	))
+}
+
+pub async fn add_candidate<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+	Form(candidate_form): Form<CandidateForm>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_candidate_outcome(
+		controller.add_candidate(candidate_form).await?,
+		&app_state.lexicon,
+	))
+}
+
+pub async fn withdraw_candidate<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+	Form(candidate_form): Form<CandidateForm>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_candidate_outcome(
+		controller.withdraw_candidate(candidate_form).await?,
+		&app_state.lexicon,
+	))
+}
+
+pub async fn rename_candidate<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+	Form(candidate_form): Form<CandidateForm>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_candidate_outcome(
+		controller.rename_candidate(candidate_form).await?,
+		&app_state.lexicon,
+	))
+}
+
+pub async fn verify_vote_log<Store: Storage>(
+	State(app_state): State<AxumState<Store>>,
+	question: Option<Path<String>>,
+) -> Result<impl IntoResponse, AxumError> {
+	let (_, controller) = question_controller(&app_state, question);
+	Ok(show_verification(
+		&controller.verify_history().await?,
+		&controller
+			.get_voting_machine()
+			.await?
+			.get_vote_log()
+			.verify(),
 		&app_state.lexicon,
 	))
 }
@@ -1,12 +1,52 @@
+use crate::domain::generic_domains::QuestionId;
+
 #[derive(Debug, Clone)]
 pub struct WebRoutes {
 	pub index: &'static str,
 	pub results: &'static str,
 	pub vote: &'static str,
+	/// Page running the election, kept apart from the one voters see.
+	pub admin: &'static str,
+	pub open: &'static str,
+	pub close: &'static str,
+	pub proxy: &'static str,
+	pub add_candidate: &'static str,
+	pub withdraw_candidate: &'static str,
+	pub rename_candidate: &'static str,
+	pub verify: &'static str,
+	pub countdown: &'static str,
+	/// Prefix under which every other route targets a single question.
+	pub question: &'static str,
+}
+
+impl WebRoutes {
+	/// Returns `route` as seen from `question`, unchanged for the main question.
+	#[must_use]
+	pub fn scoped(&self, question: &QuestionId, route: &str) -> String {
+		if *question == QuestionId::main() {
+			return route.to_string();
+		}
+		let prefix = self.question.replace("{question}", &question.0);
+		if route == self.index {
+			prefix
+		} else {
+			prefix + route
+		}
+	}
 }
 
 pub const WEB_ROUTES: WebRoutes = WebRoutes {
 	index: "/",
 	results: "/results",
 	vote: "/vote",
+	admin: "/admin",
+	open: "/admin/open",
+	close: "/admin/close",
+	proxy: "/proxy",
+	add_candidate: "/admin/candidates/add",
+	withdraw_candidate: "/admin/candidates/withdraw",
+	rename_candidate: "/admin/candidates/rename",
+	verify: "/verify",
+	countdown: "/countdown",
+	question: "/question/{question}",
 };
@@ -3,7 +3,7 @@ use axum::{
 	Router,
 };
 
-use crate::{services::web::WebService, storage::Storage};
+use crate::storage::Storage;
 
 use super::{
 	html::{html_handlers, web_routes::WebRoutes},
@@ -14,9 +14,27 @@ pub fn make_router<Store: Storage + Clone + Send + Sync + 'static>(
 	app_state: AxumState<Store>,
 	routes: &WebRoutes,
 ) -> Router {
-	Router::new()
+	let question_routes = Router::new()
 		.route(routes.index, get(html_handlers::get_index))
 		.route(routes.vote, post(html_handlers::vote))
 		.route(routes.results, get(html_handlers::get_results))
+		.route(routes.admin, get(html_handlers::get_admin))
+		.route(routes.open, post(html_handlers::open_election))
+		.route(routes.close, post(html_handlers::close_election))
+		.route(routes.proxy, post(html_handlers::register_proxy))
+		.route(routes.add_candidate, post(html_handlers::add_candidate))
+		.route(
+			routes.withdraw_candidate,
+			post(html_handlers::withdraw_candidate),
+		)
+		.route(
+			routes.rename_candidate,
+			post(html_handlers::rename_candidate),
+		)
+		.route(routes.verify, get(html_handlers::verify_vote_log))
+		.route(routes.countdown, get(html_handlers::get_countdown));
+	question_routes
+		.clone()
+		.nest(routes.question, question_routes)
 		.with_state(app_state)
 }
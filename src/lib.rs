@@ -1,4 +1,5 @@
 pub mod app_builder;
+pub mod clock;
 pub mod configuration;
 pub mod domain;
 mod interfaces;
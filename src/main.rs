@@ -1,8 +1,7 @@
 use clap::Parser;
-use rust_moment::{app_builder::run_app, configuration::Configuration};
+use rust_moment::{app_builder::run_cli, configuration::Cli};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-	let configuration = Configuration::parse();
-	run_app(configuration).await
+	run_cli(Cli::parse()).await
 }
@@ -5,10 +5,8 @@ use tokio::{
 };
 
 use crate::{
-	interfaces::{
-		cli_interfaces::handle_line,
-		lexicon::{self, Lexicon},
-	},
+	domain::vote_log::VoteSource,
+	interfaces::{cli_interfaces::handle_line, lexicon::Lexicon},
 	service::Service,
 	storage::Storage,
 	use_cases::VotingController,
@@ -34,8 +32,8 @@ impl<Store: Storage + Send + Sync + Clone + 'static> Service<Store> for TcpServi
 		let server_endpoint = format!("127.0.0.1:{}", self.port);
 		let listener = TcpListener::bind(server_endpoint).await?;
 		loop {
-			let (stream, _) = listener.accept().await?;
-			let controller = self.controller.clone();
+			let (stream, peer) = listener.accept().await?;
+			let controller = self.controller.from_source(VoteSource::Tcp(peer));
 			let lexicon = self.lexicon.clone();
 			tokio::spawn(async move {
 				let (reader, mut writer) = stream.into_split();
@@ -50,7 +48,7 @@ impl<Store: Storage + Send + Sync + Clone + 'static> Service<Store> for TcpServi
 						.await?;
 					writer.flush().await?;
 				}
-				return Ok::<(), anyhow::Error>(());
+				Ok::<(), anyhow::Error>(())
 			});
 		}
 	}
@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use tokio::net::UdpSocket;
 
 use crate::{
+	domain::vote_log::VoteSource,
 	interfaces::{cli_interfaces::handle_line, lexicon::Lexicon},
 	service::Service,
 	storage::Storage,
@@ -33,9 +34,13 @@ impl<Store: Storage + Send + Sync + Clone> Service<Store> for UdpService<Store>
 			let message = String::from_utf8_lossy(&buf[..len - 1]);
 			socket
 				.send_to(
-					handle_line(&message, self.controller.clone(), &self.lexicon)
-						.await?
-						.as_bytes(),
+					handle_line(
+						&message,
+						self.controller.from_source(VoteSource::Udp(src)),
+						&self.lexicon,
+					)
+					.await?
+					.as_bytes(),
 					&src,
 				)
 				.await?;
@@ -1,10 +1,17 @@
-use std::{fmt::format, net::SocketAddr};
+use std::net::SocketAddr;
 
 use async_trait::async_trait;
 use axum::Router;
 
 use crate::{
-	interfaces::lexicon::Lexicon, service::Service, storage::Storage, use_cases::VotingController,
+	domain::vote_log::VoteSource,
+	interfaces::{
+		lexicon::Lexicon,
+		web_interfaces::{html::web_routes::WEB_ROUTES, router::make_router, AxumState},
+	},
+	service::Service,
+	storage::Storage,
+	use_cases::VotingController,
 };
 
 pub struct WebService {
@@ -15,15 +22,20 @@ pub struct WebService {
 #[async_trait]
 impl<Store: Storage + Send + Sync + Clone + 'static> Service<Store> for WebService {
 	fn new(port: u16, lexicon: Lexicon, controller: VotingController<Store>) -> Self {
+		let app_state = AxumState {
+			controller: controller.from_source(VoteSource::Web),
+			routes: WEB_ROUTES,
+			lexicon,
+		};
 		Self {
-			address: format!("127.0.0.1:{}", port).parse().unwrap(),
-			router: Router::new(),
+			address: SocketAddr::from(([127, 0, 0, 1], port)),
+			router: make_router(app_state, &WEB_ROUTES),
 		}
 	}
 
 	async fn serve(self) -> Result<(), anyhow::Error> {
-		let listener = tokio::net::TcpListener::bind(&self.address).await.unwrap();
-		axum::serve(listener, self.router.clone()).await.unwrap();
+		let listener = tokio::net::TcpListener::bind(&self.address).await?;
+		axum::serve(listener, self.router).await?;
 		Ok(())
 	}
 }
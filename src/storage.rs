@@ -1,14 +1,95 @@
+use std::path::PathBuf;
+
 use async_trait::async_trait;
 
-use crate::domain::voting_machine::VotingMachine;
+use crate::domain::{
+	vote_log::ChainVerification,
+	voting_event::{ChainedEvent, RecordedEvent, Snapshot},
+	voting_session::VotingSession,
+};
+
+/// How a running instance shares its store with the other processes
+/// using it.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum LockMode {
+	/// The store is refused to any other instance while this one runs.
+	#[default]
+	Exclusive,
+	/// Instances started in this mode use the store together, one update at
+	/// a time.
+	Shared,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct StoreOptions {
+	pub lock: LockMode,
+	/// File the store is kept in, for the stores kept in one; each picks its
+	/// own when unset.
+	pub path: Option<PathBuf>,
+}
 
+/// Keeps the events of a session in the order they happened, along with
+/// snapshots that save replaying them from the start, and the session as it
+/// stands after the last event.
+///
+/// The events of a secret question keep no choice, so its counts come from
+/// the current session only.
 #[async_trait]
 pub trait Storage
 where
 	Self: Sized,
 	Self: Clone,
+	Self: Send + Sync,
 {
-	async fn new(machine: VotingMachine) -> anyhow::Result<Self>;
-	async fn get_voting_machine(&self) -> anyhow::Result<VotingMachine>;
-	async fn put_voting_machine(&mut self, machine: VotingMachine) -> anyhow::Result<()>;
+	/// Creates a store whose history starts from `session`.
+	async fn new(session: VotingSession) -> anyhow::Result<Self>;
+	/// Creates the store of a running instance, claimed as `options` says.
+	async fn open(session: VotingSession, _options: StoreOptions) -> anyhow::Result<Self> {
+		Self::new(session).await
+	}
+	/// Keeps other processes from updating the store until `end_update`,
+	/// so that what is read in between is still current when written.
+	async fn begin_update(&self) -> anyhow::Result<()> {
+		Ok(())
+	}
+	async fn end_update(&self) -> anyhow::Result<()> {
+		Ok(())
+	}
+	/// Returns the latest snapshot taken after at most `events` events, the
+	/// current session included.
+	async fn get_snapshot(&self, events: usize) -> anyhow::Result<Snapshot>;
+	async fn put_snapshot(&mut self, snapshot: Snapshot) -> anyhow::Result<()>;
+	/// Returns every event, with the hashes stored along with it.
+	async fn get_chain(&self) -> anyhow::Result<Vec<ChainedEvent>>;
+	/// Appends `event`, chained to the last event stored, and keeps `current`,
+	/// the session it led to, in place of the previous one.
+	async fn append_event(&mut self, event: RecordedEvent, current: Snapshot)
+		-> anyhow::Result<()>;
+
+	async fn get_events(&self) -> anyhow::Result<Vec<RecordedEvent>> {
+		Ok(self
+			.get_chain()
+			.await?
+			.into_iter()
+			.map(|chained| chained.recorded)
+			.collect())
+	}
+
+	/// Checks the stored events against the hashes stored with them.
+	async fn verify_events(&self) -> anyhow::Result<ChainVerification> {
+		Ok(ChainedEvent::verify(&self.get_chain().await?))
+	}
+
+	/// Rebuilds the session as it stood after the first `events` events.
+	async fn get_voting_session_at(&self, events: usize) -> anyhow::Result<Snapshot> {
+		let history = self.get_events().await?;
+		let events = events.min(history.len());
+		let snapshot = self.get_snapshot(events).await?;
+		let replayed = &history[snapshot.events..events];
+		Ok(snapshot.replay(replayed))
+	}
+
+	async fn get_voting_session(&self) -> anyhow::Result<VotingSession> {
+		Ok(self.get_voting_session_at(usize::MAX).await?.session)
+	}
 }
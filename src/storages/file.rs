@@ -1,73 +1,387 @@
 use std::{
 	collections::{BTreeMap, BTreeSet},
+	fs::{OpenOptions, TryLockError},
+	net::SocketAddr,
 	path::Path,
+	sync::Arc,
 };
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use tokio::{
-	fs::File,
+	fs::{self, File},
 	io::{AsyncReadExt, AsyncWriteExt},
 };
 
 use crate::{
 	domain::{
-		generic_domains::{AttendenceSheet, Candidate, Score, Voter},
+		ballot_box::BallotBox,
+		ballot_paper::{BallotPaper, Choice},
+		candidate_change::{AuditEntry, WithdrawalPolicy},
+		candidate_profile::CandidateProfile,
+		counted_ballot::CountedBallot,
+		election_mode::ElectionMode,
+		election_state::ElectionState,
+		electoral_roll::ElectoralRoll,
+		generic_domains::{AttendenceSheet, Candidate, QuestionId, Score, Voter},
+		identity::{AccentPolicy, CasePolicy, IdentityPolicy},
+		proxy::Proxies,
+		receipt::Receipt,
+		recount::Tally,
+		round::ArchivedRound,
+		schedule::Schedule,
 		scoreboard::Scoreboard,
+		tie_break::{TieBreak, TieBreakPolicy, TieResolution},
+		vote_log::{Timestamp, VoteLog, VoteLogEntry, VoteSource},
+		vote_outcome::VoteOutcome,
+		voting_event::{ChainedEvent, RecordedEvent, Snapshot, VotingEvent},
 		voting_machine::VotingMachine,
+		voting_session::VotingSession,
 	},
-	storage::Storage,
+	storage::{LockMode, Storage, StoreOptions},
 };
 
 #[derive(Clone)]
 pub struct FileStore {
 	filepath: String,
+	/// Locks held by the running instance, none for a store only read.
+	locks: Option<Arc<StoreLocks>>,
+}
+
+/// Advisory locks taken next to the store file, released when dropped.
+///
+/// The store file itself cannot carry them, since each write replaces it.
+struct StoreLocks {
+	/// Held for as long as the instance runs, shared or not as its mode says.
+	_instance: std::fs::File,
+	/// Held over each update in shared mode, where other instances write too.
+	updates: Option<Arc<std::fs::File>>,
 }
 
 const FILEPATH: &str = "machine.json";
 
+/// Reported instead of a parse error when a store file stops short, which
+/// only happens when a write to it was cut off.
+#[derive(Error, Debug)]
+#[error("{0} is cut short, a write to it was interrupted; restore it from a backup")]
+pub struct HalfWrittenFile(String);
+
+/// Reported at startup when another instance holds the store.
+#[derive(Error, Debug)]
+#[error(
+	"{0} is used by another running instance; stop it, or start every instance with `--store-lock shared`"
+)]
+pub struct StoreInUse(String);
+
+/// Reported at startup when the store on the disk was created for other
+/// candidates than the ones given, rather than carrying on with them.
+#[derive(Error, Debug)]
+#[error(
+	"{path} was created with the candidates [{stored}] for question {question}, not [{requested}]; pick another `--store-path` or `--election`"
+)]
+pub struct CandidatesDiffer {
+	path: String,
+	question: QuestionId,
+	stored: String,
+	requested: String,
+}
+
+/// Reported when the store keeps the scores of a question without the ballots
+/// they count, which only happens when the file was edited.
+#[derive(Error, Debug)]
+#[error("{path} keeps the scores of question {question} without its ballots")]
+pub struct MissingBallots {
+	path: String,
+	question: String,
+}
+
+/// Opens the lock file `path`, creating it when missing.
+fn open_lock_file(path: &str) -> anyhow::Result<std::fs::File> {
+	Ok(OpenOptions::new()
+		.create(true)
+		.truncate(false)
+		.write(true)
+		.open(path)?)
+}
+
 impl FileStore {
-	pub async fn create(machine: VotingMachine, filepath: &str) -> anyhow::Result<Self> {
-		if !Path::new(filepath).exists() {
-			let mut file = File::create(filepath).await?;
-			file.write_all(serde_json::to_string(&VotingMachineDAO::from(machine))?.as_bytes())
-				.await?;
+	/// Creates a store on `filepath` without claiming it, for reading it or
+	/// for tests. A running instance goes through `open_locked` instead.
+	pub async fn create(session: VotingSession, filepath: &str) -> anyhow::Result<Self> {
+		let store = Self {
+			filepath: filepath.to_string(),
+			locks: None,
+		};
+		store.initialize(session).await?;
+		Ok(store)
+	}
+
+	/// Creates a store on `filepath` and claims it as `mode` says until the
+	/// store and all its clones are dropped.
+	///
+	/// # Errors
+	///
+	/// Will return a `StoreInUse` error if another instance holds the store
+	/// in a way `mode` cannot share
+	pub async fn open_locked(
+		session: VotingSession,
+		filepath: &str,
+		mode: LockMode,
+	) -> anyhow::Result<Self> {
+		let instance = open_lock_file(&format!("{filepath}.lock"))?;
+		let claimed = match mode {
+			LockMode::Exclusive => instance.try_lock(),
+			LockMode::Shared => instance.try_lock_shared(),
+		};
+		match claimed {
+			Ok(()) => {}
+			Err(TryLockError::WouldBlock) => return Err(StoreInUse(filepath.to_string()).into()),
+			Err(TryLockError::Error(error)) => return Err(error.into()),
 		}
-		Ok(Self {
+		let updates = match mode {
+			LockMode::Exclusive => None,
+			LockMode::Shared => Some(Arc::new(open_lock_file(&format!(
+				"{filepath}.update.lock"
+			))?)),
+		};
+		let store = Self {
 			filepath: filepath.to_string(),
-		})
+			locks: Some(Arc::new(StoreLocks {
+				_instance: instance,
+				updates,
+			})),
+		};
+		store.begin_update().await?;
+		let claimed = store.claim(session).await;
+		store.end_update().await?;
+		claimed?;
+		Ok(store)
 	}
-}
 
-#[async_trait]
-impl Storage for FileStore {
-	async fn new(machine: VotingMachine) -> anyhow::Result<Self> {
-		Self::create(machine, FILEPATH).await
+	/// Initializes the store with `session`, or checks that the store already
+	/// on the disk was created for the same candidates.
+	async fn claim(&self, session: VotingSession) -> anyhow::Result<()> {
+		if !Path::new(&self.filepath).exists() {
+			return self.initialize(session).await;
+		}
+		let stored = self.get_snapshot(0).await?.session.get_candidates();
+		let requested = session.get_candidates();
+		let questions: BTreeSet<&QuestionId> = stored.keys().chain(requested.keys()).collect();
+		let list = |candidates: Option<&Vec<Candidate>>| {
+			candidates
+				.into_iter()
+				.flatten()
+				.map(|candidate| candidate.0.as_str())
+				.collect::<Vec<&str>>()
+				.join(", ")
+		};
+		for question in questions {
+			if stored.get(question) != requested.get(question) {
+				return Err(CandidatesDiffer {
+					path: self.filepath.clone(),
+					question: question.clone(),
+					stored: list(stored.get(question)),
+					requested: list(requested.get(question)),
+				}
+				.into());
+			}
+		}
+		Ok(())
 	}
 
-	async fn get_voting_machine(&self) -> anyhow::Result<VotingMachine> {
+	/// Writes the history of a new store, unless the file already holds one.
+	async fn initialize(&self, session: VotingSession) -> anyhow::Result<()> {
+		if !Path::new(&self.filepath).exists() {
+			self.write_history(History {
+				snapshots: vec![Snapshot { session, events: 0 }],
+				events: Vec::new(),
+				current: None,
+			})
+			.await?;
+		}
+		Ok(())
+	}
+
+	/// Lock file taken over each update, when other instances may write.
+	fn update_lock(&self) -> Option<Arc<std::fs::File>> {
+		self.locks.as_ref().and_then(|locks| locks.updates.clone())
+	}
+
+	async fn read_history(&self) -> anyhow::Result<History> {
+		Ok(History::from(self.read_stored().await?))
+	}
+
+	/// Tells whether the file holds a history, whose ballots are stored apart
+	/// from its scores, rather than the lone session or machine of a file
+	/// written before.
+	///
+	/// # Errors
+	///
+	/// Will return `Err` if the file cannot be read
+	pub async fn keeps_ballots(&self) -> anyhow::Result<bool> {
+		Ok(matches!(self.read_stored().await?, StoredDAO::History(_)))
+	}
+
+	async fn read_stored(&self) -> anyhow::Result<StoredDAO> {
 		let mut file = File::open(self.filepath.clone()).await?;
 		let file_string = &mut Vec::new();
 		file.read_to_end(file_string).await?;
 		let mut deserializer = serde_json::Deserializer::from_slice(file_string);
-		Ok(VotingMachine::from(VotingMachineDAO::deserialize(
-			&mut deserializer,
-		)?))
+		let stored = StoredDAO::deserialize(&mut deserializer).map_err(|error| {
+			if error.is_eof() {
+				anyhow!(HalfWrittenFile(self.filepath.clone()))
+			} else {
+				anyhow!(error).context(format!("{} is not a valid store", self.filepath))
+			}
+		})?;
+		if let StoredDAO::History(history) = &stored {
+			if let Some(question) = history.question_without_ballots() {
+				return Err(anyhow!(MissingBallots {
+					path: self.filepath.clone(),
+					question: question.clone(),
+				}));
+			}
+		}
+		Ok(stored)
 	}
 
-	async fn put_voting_machine(&mut self, machine: VotingMachine) -> anyhow::Result<()> {
-		let mut file = File::create(self.filepath.clone()).await?;
-		let voting_machine_json = serde_json::to_string(&VotingMachineDAO::from(machine))?;
-		file.write_all(voting_machine_json.as_bytes()).await?;
+	/// File the history is written to before it replaces the store file.
+	fn temporary_path(&self) -> String {
+		format!("{}.tmp", self.filepath)
+	}
+
+	/// Writes the history next to the store file, flushes it to the disk,
+	/// then renames it over the store file. A write cut off at any point
+	/// leaves either the previous history or the new one, never a mix.
+	async fn write_history(&self, history: History) -> anyhow::Result<()> {
+		let history_json = serde_json::to_string(&HistoryDAO::from(history))?;
+		let temporary_path = self.temporary_path();
+		let mut file = File::create(&temporary_path).await?;
+		file.write_all(history_json.as_bytes()).await?;
+		file.sync_all().await?;
+		drop(file);
+		fs::rename(&temporary_path, &self.filepath).await?;
+		// The rename only lasts once the directory holding it is on the disk.
+		let directory = match Path::new(&self.filepath).parent() {
+			Some(parent) if !parent.as_os_str().is_empty() => parent,
+			_ => Path::new("."),
+		};
+		File::open(directory).await?.sync_all().await?;
 		Ok(())
 	}
 }
 
+#[async_trait]
+impl Storage for FileStore {
+	async fn new(session: VotingSession) -> anyhow::Result<Self> {
+		Self::create(session, FILEPATH).await
+	}
+
+	async fn open(session: VotingSession, options: StoreOptions) -> anyhow::Result<Self> {
+		let filepath = match &options.path {
+			Some(path) => path
+				.to_str()
+				.ok_or_else(|| anyhow!("{} is not a valid path", path.display()))?,
+			None => FILEPATH,
+		};
+		Self::open_locked(session, filepath, options.lock).await
+	}
+
+	async fn begin_update(&self) -> anyhow::Result<()> {
+		if let Some(lock) = self.update_lock() {
+			tokio::task::spawn_blocking(move || lock.lock()).await??;
+		}
+		Ok(())
+	}
+
+	async fn end_update(&self) -> anyhow::Result<()> {
+		if let Some(lock) = self.update_lock() {
+			lock.unlock()?;
+		}
+		Ok(())
+	}
+
+	async fn get_snapshot(&self, events: usize) -> anyhow::Result<Snapshot> {
+		let history = self.read_history().await?;
+		Snapshot::latest(&history.snapshots, history.current.as_ref(), events)
+			.cloned()
+			.ok_or_else(|| anyhow!("{} holds no snapshot before event {events}", self.filepath))
+	}
+
+	async fn put_snapshot(&mut self, snapshot: Snapshot) -> anyhow::Result<()> {
+		let mut history = self.read_history().await?;
+		history.snapshots.push(snapshot);
+		self.write_history(history).await
+	}
+
+	async fn get_chain(&self) -> anyhow::Result<Vec<ChainedEvent>> {
+		Ok(self.read_history().await?.events)
+	}
+
+	async fn append_event(
+		&mut self,
+		event: RecordedEvent,
+		current: Snapshot,
+	) -> anyhow::Result<()> {
+		let mut history = self.read_history().await?;
+		let chained = ChainedEvent::following(&history.events, event);
+		history.events.push(chained);
+		history.current = Some(current);
+		self.write_history(history).await
+	}
+}
+
+/// What a store file holds: the snapshots, starting with the session the
+/// store was created with, then every event in order, then the session the
+/// last event led to.
+struct History {
+	snapshots: Vec<Snapshot>,
+	events: Vec<ChainedEvent>,
+	current: Option<Snapshot>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CandidateProfileDAO {
+	display_name: String,
+	#[serde(default)]
+	party: Option<String>,
+	#[serde(default)]
+	statement: Option<String>,
+	#[serde(default)]
+	image: Option<String>,
+}
+
+impl From<CandidateProfile> for CandidateProfileDAO {
+	fn from(value: CandidateProfile) -> Self {
+		Self {
+			display_name: value.display_name,
+			party: value.party,
+			statement: value.statement,
+			image: value.image,
+		}
+	}
+}
+
+impl From<CandidateProfileDAO> for CandidateProfile {
+	fn from(value: CandidateProfileDAO) -> Self {
+		Self {
+			display_name: value.display_name,
+			party: value.party,
+			statement: value.statement,
+			image: value.image,
+		}
+	}
+}
+
 #[derive(Serialize, Deserialize)]
 struct ScoreboardDAO {
 	scores: BTreeMap<String, usize>,
 	blank_scores: usize,
 	invalid_scores: usize,
+	#[serde(default)]
+	profiles: BTreeMap<String, CandidateProfileDAO>,
 }
 
 impl From<Scoreboard> for ScoreboardDAO {
@@ -76,10 +390,16 @@ impl From<Scoreboard> for ScoreboardDAO {
 		for (candidate, score) in value.scores {
 			scores.insert(candidate.0, score.0);
 		}
+		let profiles = value
+			.profiles
+			.into_iter()
+			.map(|(candidate, profile)| (candidate.0, CandidateProfileDAO::from(profile)))
+			.collect();
 		Self {
 			scores,
 			blank_scores: value.blank_score.0,
 			invalid_scores: value.invalid_score.0,
+			profiles,
 		}
 	}
 }
@@ -94,14 +414,578 @@ impl From<ScoreboardDAO> for Scoreboard {
 			scores,
 			blank_score: Score(value.blank_scores),
 			invalid_score: Score(value.invalid_scores),
+			profiles: value
+				.profiles
+				.into_iter()
+				.map(|(candidate, profile)| (Candidate(candidate), CandidateProfile::from(profile)))
+				.collect(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Default)]
+enum ElectionModeDAO {
+	#[default]
+	Plurality,
+	TwoRound,
+	InstantRunoff,
+	Approval,
+	Schulze,
+	SingleTransferableVote {
+		seats: usize,
+	},
+}
+
+impl From<ElectionMode> for ElectionModeDAO {
+	fn from(value: ElectionMode) -> Self {
+		match value {
+			ElectionMode::Plurality => Self::Plurality,
+			ElectionMode::TwoRound => Self::TwoRound,
+			ElectionMode::InstantRunoff => Self::InstantRunoff,
+			ElectionMode::Approval => Self::Approval,
+			ElectionMode::Schulze => Self::Schulze,
+			ElectionMode::SingleTransferableVote { seats } => {
+				Self::SingleTransferableVote { seats }
+			}
+		}
+	}
+}
+
+impl From<ElectionModeDAO> for ElectionMode {
+	fn from(value: ElectionModeDAO) -> Self {
+		match value {
+			ElectionModeDAO::Plurality => Self::Plurality,
+			ElectionModeDAO::TwoRound => Self::TwoRound,
+			ElectionModeDAO::InstantRunoff => Self::InstantRunoff,
+			ElectionModeDAO::Approval => Self::Approval,
+			ElectionModeDAO::Schulze => Self::Schulze,
+			ElectionModeDAO::SingleTransferableVote { seats } => {
+				Self::SingleTransferableVote { seats }
+			}
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Default)]
+enum ElectionStateDAO {
+	NotOpen,
+	#[default]
+	Open,
+	Closed,
+}
+
+impl From<ElectionState> for ElectionStateDAO {
+	fn from(value: ElectionState) -> Self {
+		match value {
+			ElectionState::NotOpen => Self::NotOpen,
+			ElectionState::Open => Self::Open,
+			ElectionState::Closed => Self::Closed,
+		}
+	}
+}
+
+impl From<ElectionStateDAO> for ElectionState {
+	fn from(value: ElectionStateDAO) -> Self {
+		match value {
+			ElectionStateDAO::NotOpen => Self::NotOpen,
+			ElectionStateDAO::Open => Self::Open,
+			ElectionStateDAO::Closed => Self::Closed,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchivedRoundDAO {
+	voters: BTreeSet<String>,
+	#[serde(default)]
+	cast_by: BTreeMap<String, String>,
+	scoreboard: ScoreboardDAO,
+}
+
+impl From<ArchivedRound> for ArchivedRoundDAO {
+	fn from(value: ArchivedRound) -> Self {
+		let mut voters = BTreeSet::new();
+		for voter in value.voters.0 {
+			voters.insert(voter.0);
+		}
+
+		let cast_by = voter_pairs_to_dao(value.voters.1);
+		let scoreboard = ScoreboardDAO::from(value.scoreboard);
+		Self {
+			voters,
+			cast_by,
+			scoreboard,
+		}
+	}
+}
+
+impl From<ArchivedRoundDAO> for ArchivedRound {
+	fn from(value: ArchivedRoundDAO) -> Self {
+		let mut voters = BTreeSet::new();
+		for voter in value.voters {
+			voters.insert(Voter(voter));
+		}
+
+		Self {
+			voters: AttendenceSheet(voters, voter_pairs_from_dao(value.cast_by)),
+			scoreboard: Scoreboard::from(value.scoreboard),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Default)]
+enum TieBreakPolicyDAO {
+	#[default]
+	Unresolved,
+	Oldest(BTreeMap<String, String>),
+	Draw(u64),
+	Runoff,
+}
+
+impl From<TieBreakPolicy> for TieBreakPolicyDAO {
+	fn from(value: TieBreakPolicy) -> Self {
+		match value {
+			TieBreakPolicy::Unresolved => Self::Unresolved,
+			TieBreakPolicy::Oldest(birth_dates) => Self::Oldest(
+				birth_dates
+					.into_iter()
+					.map(|(candidate, birth_date)| (candidate.0, birth_date.to_string()))
+					.collect(),
+			),
+			TieBreakPolicy::Draw(seed) => Self::Draw(seed),
+			TieBreakPolicy::Runoff => Self::Runoff,
+		}
+	}
+}
+
+impl From<TieBreakPolicyDAO> for TieBreakPolicy {
+	fn from(value: TieBreakPolicyDAO) -> Self {
+		match value {
+			TieBreakPolicyDAO::Unresolved => Self::Unresolved,
+			TieBreakPolicyDAO::Oldest(birth_dates) => Self::Oldest(
+				birth_dates
+					.into_iter()
+					.filter_map(|(candidate, birth_date)| {
+						Some((Candidate(candidate), birth_date.parse().ok()?))
+					})
+					.collect(),
+			),
+			TieBreakPolicyDAO::Draw(seed) => Self::Draw(seed),
+			TieBreakPolicyDAO::Runoff => Self::Runoff,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum TieResolutionDAO {
+	Winner(String),
+	Runoff(Vec<String>),
+	Unresolved,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TieBreakDAO {
+	tied: Vec<String>,
+	resolution: TieResolutionDAO,
+}
+
+impl From<TieBreak> for TieBreakDAO {
+	fn from(value: TieBreak) -> Self {
+		let resolution = match value.resolution {
+			TieResolution::Winner(winner) => TieResolutionDAO::Winner(winner.0),
+			TieResolution::Runoff(candidates) => TieResolutionDAO::Runoff(
+				candidates
+					.into_iter()
+					.map(|candidate| candidate.0)
+					.collect(),
+			),
+			TieResolution::Unresolved => TieResolutionDAO::Unresolved,
+		};
+		Self {
+			tied: value
+				.tied
+				.into_iter()
+				.map(|candidate| candidate.0)
+				.collect(),
+			resolution,
+		}
+	}
+}
+
+impl From<TieBreakDAO> for TieBreak {
+	fn from(value: TieBreakDAO) -> Self {
+		let resolution = match value.resolution {
+			TieResolutionDAO::Winner(winner) => TieResolution::Winner(Candidate(winner)),
+			TieResolutionDAO::Runoff(candidates) => {
+				TieResolution::Runoff(candidates.into_iter().map(Candidate).collect())
+			}
+			TieResolutionDAO::Unresolved => TieResolution::Unresolved,
+		};
+		Self {
+			tied: value.tied.into_iter().map(Candidate).collect(),
+			resolution,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum CountedBallotDAO {
+	Candidate(String),
+	Ranked(Vec<String>),
+	Approved(Vec<String>),
+	Blank,
+	Invalid,
+}
+
+impl From<CountedBallot> for CountedBallotDAO {
+	fn from(value: CountedBallot) -> Self {
+		let names = |candidates: Vec<Candidate>| {
+			candidates
+				.into_iter()
+				.map(|candidate| candidate.0)
+				.collect()
+		};
+		match value {
+			CountedBallot::Candidate(candidate) => Self::Candidate(candidate.0),
+			CountedBallot::Ranked(ranking) => Self::Ranked(names(ranking)),
+			CountedBallot::Approved(approved) => Self::Approved(names(approved)),
+			CountedBallot::Blank => Self::Blank,
+			CountedBallot::Invalid => Self::Invalid,
+		}
+	}
+}
+
+impl From<CountedBallotDAO> for CountedBallot {
+	fn from(value: CountedBallotDAO) -> Self {
+		let candidates = |names: Vec<String>| names.into_iter().map(Candidate).collect();
+		match value {
+			CountedBallotDAO::Candidate(candidate) => Self::Candidate(Candidate(candidate)),
+			CountedBallotDAO::Ranked(ranking) => Self::Ranked(candidates(ranking)),
+			CountedBallotDAO::Approved(approved) => Self::Approved(candidates(approved)),
+			CountedBallotDAO::Blank => Self::Blank,
+			CountedBallotDAO::Invalid => Self::Invalid,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum VoteOutcomeDAO {
+	AcceptedVote(String, String),
+	AcceptedRankedVote(String, Vec<String>),
+	AcceptedApprovalVote(String, Vec<String>, Vec<String>),
+	BlankVote(String),
+	InvalidVote(String),
+	VoteChanged(Box<VoteOutcomeDAO>),
+	BallotCast(String),
+	HasAlreadyVoted(String),
+	NotRegistered(String),
+	NotProxyHolder(String, String),
+	ElectionNotOpen(String),
+	ElectionClosed(String),
+	DeadlinePassed(String),
+}
+
+impl From<VoteOutcome> for VoteOutcomeDAO {
+	fn from(value: VoteOutcome) -> Self {
+		let names = |candidates: Vec<Candidate>| {
+			candidates
+				.into_iter()
+				.map(|candidate| candidate.0)
+				.collect()
+		};
+		match value {
+			VoteOutcome::AcceptedVote(voter, candidate) => Self::AcceptedVote(voter.0, candidate.0),
+			VoteOutcome::AcceptedRankedVote(voter, ranking) => {
+				Self::AcceptedRankedVote(voter.0, names(ranking))
+			}
+			VoteOutcome::AcceptedApprovalVote(voter, approved, unknown) => {
+				Self::AcceptedApprovalVote(voter.0, names(approved), names(unknown))
+			}
+			VoteOutcome::BlankVote(voter) => Self::BlankVote(voter.0),
+			VoteOutcome::InvalidVote(voter) => Self::InvalidVote(voter.0),
+			VoteOutcome::VoteChanged(outcome) => Self::VoteChanged(Box::new((*outcome).into())),
+			VoteOutcome::BallotCast(voter) => Self::BallotCast(voter.0),
+			VoteOutcome::HasAlreadyVoted(voter) => Self::HasAlreadyVoted(voter.0),
+			VoteOutcome::NotRegistered(voter) => Self::NotRegistered(voter.0),
+			VoteOutcome::NotProxyHolder(holder, voter) => Self::NotProxyHolder(holder.0, voter.0),
+			VoteOutcome::ElectionNotOpen(voter) => Self::ElectionNotOpen(voter.0),
+			VoteOutcome::ElectionClosed(voter) => Self::ElectionClosed(voter.0),
+			VoteOutcome::DeadlinePassed(voter) => Self::DeadlinePassed(voter.0),
+		}
+	}
+}
+
+impl From<VoteOutcomeDAO> for VoteOutcome {
+	fn from(value: VoteOutcomeDAO) -> Self {
+		let candidates = |names: Vec<String>| names.into_iter().map(Candidate).collect();
+		match value {
+			VoteOutcomeDAO::AcceptedVote(voter, candidate) => {
+				Self::AcceptedVote(Voter(voter), Candidate(candidate))
+			}
+			VoteOutcomeDAO::AcceptedRankedVote(voter, ranking) => {
+				Self::AcceptedRankedVote(Voter(voter), candidates(ranking))
+			}
+			VoteOutcomeDAO::AcceptedApprovalVote(voter, approved, unknown) => {
+				Self::AcceptedApprovalVote(Voter(voter), candidates(approved), candidates(unknown))
+			}
+			VoteOutcomeDAO::BlankVote(voter) => Self::BlankVote(Voter(voter)),
+			VoteOutcomeDAO::InvalidVote(voter) => Self::InvalidVote(Voter(voter)),
+			VoteOutcomeDAO::VoteChanged(outcome) => Self::VoteChanged(Box::new((*outcome).into())),
+			VoteOutcomeDAO::BallotCast(voter) => Self::BallotCast(Voter(voter)),
+			VoteOutcomeDAO::HasAlreadyVoted(voter) => Self::HasAlreadyVoted(Voter(voter)),
+			VoteOutcomeDAO::NotRegistered(voter) => Self::NotRegistered(Voter(voter)),
+			VoteOutcomeDAO::NotProxyHolder(holder, voter) => {
+				Self::NotProxyHolder(Voter(holder), Voter(voter))
+			}
+			VoteOutcomeDAO::ElectionNotOpen(voter) => Self::ElectionNotOpen(Voter(voter)),
+			VoteOutcomeDAO::ElectionClosed(voter) => Self::ElectionClosed(Voter(voter)),
+			VoteOutcomeDAO::DeadlinePassed(voter) => Self::DeadlinePassed(Voter(voter)),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum VoteSourceDAO {
+	Stdio,
+	Tcp(SocketAddr),
+	Udp(SocketAddr),
+	Web,
+}
+
+impl From<VoteSource> for VoteSourceDAO {
+	fn from(value: VoteSource) -> Self {
+		match value {
+			VoteSource::Stdio => Self::Stdio,
+			VoteSource::Tcp(peer) => Self::Tcp(peer),
+			VoteSource::Udp(peer) => Self::Udp(peer),
+			VoteSource::Web => Self::Web,
 		}
 	}
 }
 
+impl From<VoteSourceDAO> for VoteSource {
+	fn from(value: VoteSourceDAO) -> Self {
+		match value {
+			VoteSourceDAO::Stdio => Self::Stdio,
+			VoteSourceDAO::Tcp(peer) => Self::Tcp(peer),
+			VoteSourceDAO::Udp(peer) => Self::Udp(peer),
+			VoteSourceDAO::Web => Self::Web,
+		}
+	}
+}
+
+/// Keeps the stored hashes as they are, so that an edited file fails verification.
+#[derive(Serialize, Deserialize)]
+struct VoteLogEntryDAO {
+	timestamp: u64,
+	source: VoteSourceDAO,
+	outcome: VoteOutcomeDAO,
+	previous: String,
+	hash: String,
+}
+
+impl From<VoteLogEntry> for VoteLogEntryDAO {
+	fn from(value: VoteLogEntry) -> Self {
+		Self {
+			timestamp: value.timestamp.0,
+			source: value.source.into(),
+			outcome: value.outcome.into(),
+			previous: value.previous,
+			hash: value.hash,
+		}
+	}
+}
+
+impl From<VoteLogEntryDAO> for VoteLogEntry {
+	fn from(value: VoteLogEntryDAO) -> Self {
+		Self {
+			timestamp: Timestamp(value.timestamp),
+			source: value.source.into(),
+			outcome: value.outcome.into(),
+			previous: value.previous,
+			hash: value.hash,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum WithdrawalPolicyDAO {
+	Keep,
+	Invalidate,
+	Redistribute,
+}
+
+impl From<WithdrawalPolicy> for WithdrawalPolicyDAO {
+	fn from(value: WithdrawalPolicy) -> Self {
+		match value {
+			WithdrawalPolicy::Keep => Self::Keep,
+			WithdrawalPolicy::Invalidate => Self::Invalidate,
+			WithdrawalPolicy::Redistribute => Self::Redistribute,
+		}
+	}
+}
+
+impl From<WithdrawalPolicyDAO> for WithdrawalPolicy {
+	fn from(value: WithdrawalPolicyDAO) -> Self {
+		match value {
+			WithdrawalPolicyDAO::Keep => Self::Keep,
+			WithdrawalPolicyDAO::Invalidate => Self::Invalidate,
+			WithdrawalPolicyDAO::Redistribute => Self::Redistribute,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum AuditEntryDAO {
+	Added(String),
+	Withdrawn(String, WithdrawalPolicyDAO),
+	Renamed(String, String),
+}
+
+impl From<AuditEntry> for AuditEntryDAO {
+	fn from(value: AuditEntry) -> Self {
+		match value {
+			AuditEntry::CandidateAdded(candidate) => Self::Added(candidate.0),
+			AuditEntry::CandidateWithdrawn(candidate, policy) => {
+				Self::Withdrawn(candidate.0, policy.into())
+			}
+			AuditEntry::CandidateRenamed(candidate, new_name) => {
+				Self::Renamed(candidate.0, new_name.0)
+			}
+		}
+	}
+}
+
+impl From<AuditEntryDAO> for AuditEntry {
+	fn from(value: AuditEntryDAO) -> Self {
+		match value {
+			AuditEntryDAO::Added(candidate) => Self::CandidateAdded(Candidate(candidate)),
+			AuditEntryDAO::Withdrawn(candidate, policy) => {
+				Self::CandidateWithdrawn(Candidate(candidate), policy.into())
+			}
+			AuditEntryDAO::Renamed(candidate, new_name) => {
+				Self::CandidateRenamed(Candidate(candidate), Candidate(new_name))
+			}
+		}
+	}
+}
+
+fn voter_pairs_to_dao(pairs: BTreeMap<Voter, Voter>) -> BTreeMap<String, String> {
+	pairs
+		.into_iter()
+		.map(|(voter, holder)| (voter.0, holder.0))
+		.collect()
+}
+
+fn voter_pairs_from_dao(pairs: BTreeMap<String, String>) -> BTreeMap<Voter, Voter> {
+	pairs
+		.into_iter()
+		.map(|(voter, holder)| (Voter(voter), Voter(holder)))
+		.collect()
+}
+
+#[derive(Serialize, Deserialize, Default)]
+enum CasePolicyDAO {
+	#[default]
+	Sensitive,
+	Insensitive,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+enum AccentPolicyDAO {
+	#[default]
+	Kept,
+	Stripped,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct IdentityPolicyDAO {
+	case: CasePolicyDAO,
+	accents: AccentPolicyDAO,
+}
+
+impl From<IdentityPolicy> for IdentityPolicyDAO {
+	fn from(value: IdentityPolicy) -> Self {
+		Self {
+			case: match value.case {
+				CasePolicy::Sensitive => CasePolicyDAO::Sensitive,
+				CasePolicy::Insensitive => CasePolicyDAO::Insensitive,
+			},
+			accents: match value.accents {
+				AccentPolicy::Kept => AccentPolicyDAO::Kept,
+				AccentPolicy::Stripped => AccentPolicyDAO::Stripped,
+			},
+		}
+	}
+}
+
+impl From<IdentityPolicyDAO> for IdentityPolicy {
+	fn from(value: IdentityPolicyDAO) -> Self {
+		Self {
+			case: match value.case {
+				CasePolicyDAO::Sensitive => CasePolicy::Sensitive,
+				CasePolicyDAO::Insensitive => CasePolicy::Insensitive,
+			},
+			accents: match value.accents {
+				AccentPolicyDAO::Kept => AccentPolicy::Kept,
+				AccentPolicyDAO::Stripped => AccentPolicy::Stripped,
+			},
+		}
+	}
+}
+
+const fn default_max_proxies() -> usize {
+	1
+}
+
 #[derive(Serialize, Deserialize)]
 struct VotingMachineDAO {
 	voters: BTreeSet<String>,
+	#[serde(default)]
+	cast_by: BTreeMap<String, String>,
 	scoreboard: ScoreboardDAO,
+	#[serde(default)]
+	mode: ElectionModeDAO,
+	#[serde(default)]
+	state: ElectionStateDAO,
+	#[serde(default)]
+	roll: Option<BTreeSet<String>>,
+	#[serde(default)]
+	proxies: BTreeMap<String, String>,
+	#[serde(default = "default_max_proxies")]
+	max_proxies: usize,
+	#[serde(default)]
+	ballots: Vec<Vec<String>>,
+	#[serde(default)]
+	archived_rounds: Vec<ArchivedRoundDAO>,
+	#[serde(default)]
+	tie_break: TieBreakPolicyDAO,
+	#[serde(default)]
+	tie_breaks: Vec<TieBreakDAO>,
+	#[serde(default)]
+	revotes: bool,
+	#[serde(default)]
+	secret: bool,
+	#[serde(default)]
+	write_ins: bool,
+	#[serde(default)]
+	identity: IdentityPolicyDAO,
+	#[serde(default)]
+	counted: BTreeMap<String, CountedBallotDAO>,
+	#[serde(default)]
+	tally: Vec<CountedBallotDAO>,
+	#[serde(default)]
+	receipts: BTreeMap<String, String>,
+	#[serde(default)]
+	vote_log: Vec<VoteLogEntryDAO>,
+	#[serde(default)]
+	opens: Option<u64>,
+	#[serde(default)]
+	closes: Option<u64>,
+	#[serde(default)]
+	withdrawn: BTreeSet<String>,
+	#[serde(default)]
+	audit: Vec<AuditEntryDAO>,
 }
 
 impl From<VotingMachine> for VotingMachineDAO {
@@ -111,8 +995,100 @@ impl From<VotingMachine> for VotingMachineDAO {
 			voters.insert(voter.0);
 		}
 
+		let cast_by = voter_pairs_to_dao(value.get_voter().1.clone());
 		let scoreboard = ScoreboardDAO::from(value.get_scoreboard().clone());
-		Self { voters, scoreboard }
+		let mode = ElectionModeDAO::from(value.get_mode());
+		let state = ElectionStateDAO::from(value.get_state());
+		let roll = value
+			.get_roll()
+			.map(|roll| roll.0.iter().map(|voter| voter.0.clone()).collect());
+		let proxies = voter_pairs_to_dao(value.get_proxies().0.clone());
+		let max_proxies = value.get_max_proxies();
+		let ballots = value
+			.get_ballots()
+			.0
+			.iter()
+			.map(|ranking| {
+				ranking
+					.iter()
+					.map(|candidate| candidate.0.clone())
+					.collect()
+			})
+			.collect();
+		let archived_rounds = value
+			.get_archived_rounds()
+			.iter()
+			.cloned()
+			.map(ArchivedRoundDAO::from)
+			.collect();
+		let tie_break = TieBreakPolicyDAO::from(value.get_tie_break().clone());
+		let tie_breaks = value
+			.get_tie_breaks()
+			.iter()
+			.cloned()
+			.map(TieBreakDAO::from)
+			.collect();
+		let counted = value
+			.get_counted()
+			.iter()
+			.map(|(voter, counted)| (voter.0.clone(), CountedBallotDAO::from(counted.clone())))
+			.collect();
+		let tally = value
+			.get_tally()
+			.0
+			.iter()
+			.cloned()
+			.map(CountedBallotDAO::from)
+			.collect();
+		let receipts = value
+			.get_receipts()
+			.iter()
+			.map(|(voter, receipt)| (voter.0.clone(), receipt.0.clone()))
+			.collect();
+		let vote_log = value
+			.get_vote_log()
+			.0
+			.iter()
+			.cloned()
+			.map(VoteLogEntryDAO::from)
+			.collect();
+		let withdrawn = value
+			.get_withdrawn()
+			.iter()
+			.map(|candidate| candidate.0.clone())
+			.collect();
+		let audit = value
+			.get_audit()
+			.iter()
+			.cloned()
+			.map(AuditEntryDAO::from)
+			.collect();
+		Self {
+			voters,
+			cast_by,
+			scoreboard,
+			mode,
+			state,
+			roll,
+			proxies,
+			max_proxies,
+			ballots,
+			archived_rounds,
+			tie_break,
+			tie_breaks,
+			revotes: value.get_revotes(),
+			secret: value.get_secret(),
+			write_ins: value.get_write_ins(),
+			identity: IdentityPolicyDAO::from(*value.get_identity()),
+			counted,
+			tally,
+			receipts,
+			vote_log,
+			opens: value.get_schedule().opens.map(|opens| opens.0),
+			closes: value.get_schedule().closes.map(|closes| closes.0),
+			withdrawn,
+			audit,
+		}
 	}
 }
 
@@ -123,13 +1099,416 @@ impl From<VotingMachineDAO> for VotingMachine {
 			voters.insert(Voter(voter));
 		}
 
-		let attendence_sheet = AttendenceSheet(voters);
+		let attendence_sheet = AttendenceSheet(voters, voter_pairs_from_dao(value.cast_by));
 
 		let scoreboard = Scoreboard::from(value.scoreboard);
-		Self::new(attendence_sheet, scoreboard)
+		let ballots = value
+			.ballots
+			.into_iter()
+			.map(|ranking| ranking.into_iter().map(Candidate).collect())
+			.collect();
+		let machine = Self::new(attendence_sheet, scoreboard)
+			.with_mode(value.mode.into())
+			.with_state(value.state.into())
+			.with_proxies(Proxies(voter_pairs_from_dao(value.proxies)))
+			.with_max_proxies(value.max_proxies)
+			.with_ballots(BallotBox(ballots))
+			.with_archived_rounds(
+				value
+					.archived_rounds
+					.into_iter()
+					.map(ArchivedRound::from)
+					.collect(),
+			)
+			.with_tie_break(value.tie_break.into())
+			.with_tie_breaks(value.tie_breaks.into_iter().map(TieBreak::from).collect())
+			.with_revotes(value.revotes)
+			.with_secret(value.secret)
+			.with_write_ins(value.write_ins)
+			.with_identity(value.identity.into())
+			.with_withdrawn(value.withdrawn.into_iter().map(Candidate).collect())
+			.with_audit(value.audit.into_iter().map(AuditEntry::from).collect())
+			.with_vote_log(VoteLog(
+				value.vote_log.into_iter().map(VoteLogEntry::from).collect(),
+			))
+			.with_schedule(Schedule {
+				opens: value.opens.map(Timestamp),
+				closes: value.closes.map(Timestamp),
+			})
+			.with_counted(
+				value
+					.counted
+					.into_iter()
+					.map(|(voter, counted)| (Voter(voter), CountedBallot::from(counted)))
+					.collect(),
+			)
+			.with_tally(Tally(
+				value.tally.into_iter().map(CountedBallot::from).collect(),
+			))
+			.with_receipts(
+				value
+					.receipts
+					.into_iter()
+					.map(|(voter, receipt)| (Voter(voter), Receipt(receipt)))
+					.collect(),
+			);
+		let machine = match value.roll {
+			Some(roll) => machine.with_roll(ElectoralRoll(roll.into_iter().map(Voter).collect())),
+			None => machine,
+		};
+		// Names written before the policy was set are brought in line.
+		machine.normalized()
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct VotingSessionDAO {
+	questions: BTreeMap<String, VotingMachineDAO>,
+}
+
+impl From<VotingSession> for VotingSessionDAO {
+	fn from(value: VotingSession) -> Self {
+		let mut questions = BTreeMap::new();
+		for (question, machine) in value.get_questions().clone() {
+			questions.insert(question.0, VotingMachineDAO::from(machine));
+		}
+		Self { questions }
+	}
+}
+
+impl From<VotingSessionDAO> for VotingSession {
+	fn from(value: VotingSessionDAO) -> Self {
+		let mut session = Self::default();
+		for (question, machine) in value.questions {
+			session = session.with_question(QuestionId(question), VotingMachine::from(machine));
+		}
+		session
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum ChoiceDAO {
+	Single(Option<String>),
+	Ranked(Vec<String>),
+	Approval(Vec<String>),
+}
+
+impl From<Choice> for ChoiceDAO {
+	fn from(value: Choice) -> Self {
+		let names = |candidates: Vec<Candidate>| {
+			candidates
+				.into_iter()
+				.map(|candidate| candidate.0)
+				.collect()
+		};
+		match value {
+			Choice::Single(candidate) => Self::Single(candidate.map(|candidate| candidate.0)),
+			Choice::Ranked(ranking) => Self::Ranked(names(ranking)),
+			Choice::Approval(approved) => Self::Approval(names(approved.into_iter().collect())),
+		}
+	}
+}
+
+impl From<ChoiceDAO> for Choice {
+	fn from(value: ChoiceDAO) -> Self {
+		match value {
+			ChoiceDAO::Single(candidate) => Self::Single(candidate.map(Candidate)),
+			ChoiceDAO::Ranked(ranking) => {
+				Self::Ranked(ranking.into_iter().map(Candidate).collect())
+			}
+			ChoiceDAO::Approval(approved) => {
+				Self::Approval(approved.into_iter().map(Candidate).collect())
+			}
+		}
 	}
 }
 
+#[derive(Serialize, Deserialize)]
+struct BallotPaperDAO {
+	voter: String,
+	choice: ChoiceDAO,
+	#[serde(default)]
+	cast_by: Option<String>,
+}
+
+impl From<BallotPaper> for BallotPaperDAO {
+	fn from(value: BallotPaper) -> Self {
+		Self {
+			voter: value.voter.0,
+			choice: value.choice.into(),
+			cast_by: value.cast_by.map(|holder| holder.0),
+		}
+	}
+}
+
+impl From<BallotPaperDAO> for BallotPaper {
+	fn from(value: BallotPaperDAO) -> Self {
+		Self {
+			voter: Voter(value.voter),
+			choice: value.choice.into(),
+			cast_by: value.cast_by.map(Voter),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+enum VotingEventDAO {
+	BallotCast {
+		ballot_paper: BallotPaperDAO,
+		receipt: String,
+		timestamp: u64,
+		source: VoteSourceDAO,
+	},
+	CheckedIn {
+		voter: String,
+		cast_by: Option<String>,
+		receipt: String,
+		timestamp: u64,
+		source: VoteSourceDAO,
+	},
+	ProxyRegistered(String, String),
+	ElectionOpened,
+	ElectionClosed,
+	RoundClosed,
+	CandidateAdded(String),
+	CandidateWithdrawn(String, WithdrawalPolicyDAO),
+	CandidateRenamed(String, String),
+}
+
+impl From<VotingEvent> for VotingEventDAO {
+	fn from(value: VotingEvent) -> Self {
+		match value {
+			VotingEvent::BallotCast {
+				ballot_paper,
+				receipt,
+				timestamp,
+				source,
+			} => Self::BallotCast {
+				ballot_paper: ballot_paper.into(),
+				receipt: receipt.0,
+				timestamp: timestamp.0,
+				source: source.into(),
+			},
+			VotingEvent::CheckedIn {
+				voter,
+				cast_by,
+				receipt,
+				timestamp,
+				source,
+			} => Self::CheckedIn {
+				voter: voter.0,
+				cast_by: cast_by.map(|holder| holder.0),
+				receipt: receipt.0,
+				timestamp: timestamp.0,
+				source: source.into(),
+			},
+			VotingEvent::ProxyRegistered(voter, holder) => Self::ProxyRegistered(voter.0, holder.0),
+			VotingEvent::ElectionOpened => Self::ElectionOpened,
+			VotingEvent::ElectionClosed => Self::ElectionClosed,
+			VotingEvent::RoundClosed => Self::RoundClosed,
+			VotingEvent::CandidateAdded(candidate) => Self::CandidateAdded(candidate.0),
+			VotingEvent::CandidateWithdrawn(candidate, policy) => {
+				Self::CandidateWithdrawn(candidate.0, policy.into())
+			}
+			VotingEvent::CandidateRenamed(candidate, new_name) => {
+				Self::CandidateRenamed(candidate.0, new_name.0)
+			}
+		}
+	}
+}
+
+impl From<VotingEventDAO> for VotingEvent {
+	fn from(value: VotingEventDAO) -> Self {
+		match value {
+			VotingEventDAO::BallotCast {
+				ballot_paper,
+				receipt,
+				timestamp,
+				source,
+			} => Self::BallotCast {
+				ballot_paper: ballot_paper.into(),
+				receipt: Receipt(receipt),
+				timestamp: Timestamp(timestamp),
+				source: source.into(),
+			},
+			VotingEventDAO::CheckedIn {
+				voter,
+				cast_by,
+				receipt,
+				timestamp,
+				source,
+			} => Self::CheckedIn {
+				voter: Voter(voter),
+				cast_by: cast_by.map(Voter),
+				receipt: Receipt(receipt),
+				timestamp: Timestamp(timestamp),
+				source: source.into(),
+			},
+			VotingEventDAO::ProxyRegistered(voter, holder) => {
+				Self::ProxyRegistered(Voter(voter), Voter(holder))
+			}
+			VotingEventDAO::ElectionOpened => Self::ElectionOpened,
+			VotingEventDAO::ElectionClosed => Self::ElectionClosed,
+			VotingEventDAO::RoundClosed => Self::RoundClosed,
+			VotingEventDAO::CandidateAdded(candidate) => Self::CandidateAdded(Candidate(candidate)),
+			VotingEventDAO::CandidateWithdrawn(candidate, policy) => {
+				Self::CandidateWithdrawn(Candidate(candidate), policy.into())
+			}
+			VotingEventDAO::CandidateRenamed(candidate, new_name) => {
+				Self::CandidateRenamed(Candidate(candidate), Candidate(new_name))
+			}
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedEventDAO {
+	question: String,
+	event: VotingEventDAO,
+	/// Left empty in files written before events were chained, which then
+	/// fail verification.
+	#[serde(default)]
+	previous: String,
+	#[serde(default)]
+	hash: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotDAO {
+	events: usize,
+	session: VotingSessionDAO,
+}
+
+impl From<Snapshot> for SnapshotDAO {
+	fn from(value: Snapshot) -> Self {
+		Self {
+			events: value.events,
+			session: value.session.into(),
+		}
+	}
+}
+
+impl From<SnapshotDAO> for Snapshot {
+	fn from(value: SnapshotDAO) -> Self {
+		Self {
+			session: value.session.into(),
+			events: value.events,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct HistoryDAO {
+	snapshots: Vec<SnapshotDAO>,
+	events: Vec<RecordedEventDAO>,
+	#[serde(default)]
+	current: Option<SnapshotDAO>,
+	/// The tally of each question of `current`, kept apart from its scores so
+	/// that a recount compares two separate records.
+	#[serde(default)]
+	ballots: BTreeMap<String, Vec<CountedBallotDAO>>,
+}
+
+impl HistoryDAO {
+	/// Returns a question of `current` that has no entry in `ballots`.
+	fn question_without_ballots(&self) -> Option<&String> {
+		self.current
+			.iter()
+			.flat_map(|current| current.session.questions.keys())
+			.find(|question| !self.ballots.contains_key(*question))
+	}
+}
+
+impl From<History> for HistoryDAO {
+	fn from(value: History) -> Self {
+		let mut current = value.current.map(SnapshotDAO::from);
+		let ballots = current
+			.iter_mut()
+			.flat_map(|current| &mut current.session.questions)
+			.map(|(question, machine)| (question.clone(), std::mem::take(&mut machine.tally)))
+			.collect();
+		Self {
+			snapshots: value.snapshots.into_iter().map(SnapshotDAO::from).collect(),
+			events: value
+				.events
+				.into_iter()
+				.map(|chained| RecordedEventDAO {
+					question: chained.recorded.question.0,
+					event: chained.recorded.event.into(),
+					previous: chained.previous,
+					hash: chained.hash,
+				})
+				.collect(),
+			current,
+			ballots,
+		}
+	}
+}
+
+impl From<HistoryDAO> for History {
+	fn from(mut value: HistoryDAO) -> Self {
+		for (question, machine) in value
+			.current
+			.iter_mut()
+			.flat_map(|current| &mut current.session.questions)
+		{
+			if let Some(tally) = value.ballots.remove(question) {
+				machine.tally = tally;
+			}
+		}
+		Self {
+			snapshots: value.snapshots.into_iter().map(Snapshot::from).collect(),
+			events: value
+				.events
+				.into_iter()
+				.map(|stored| ChainedEvent {
+					recorded: RecordedEvent {
+						question: QuestionId(stored.question),
+						event: stored.event.into(),
+					},
+					previous: stored.previous,
+					hash: stored.hash,
+				})
+				.collect(),
+			current: value.current.map(Snapshot::from),
+		}
+	}
+}
+
+/// Files written before the store kept events hold a single session, or a
+/// single machine when they predate sessions with several questions.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredDAO {
+	History(HistoryDAO),
+	Session(VotingSessionDAO),
+	Machine(Box<VotingMachineDAO>),
+}
+
+impl From<StoredDAO> for History {
+	fn from(value: StoredDAO) -> Self {
+		let session = match value {
+			StoredDAO::History(history) => return Self::from(history),
+			StoredDAO::Session(session) => VotingSession::from(session),
+			StoredDAO::Machine(machine) => VotingSession::new(VotingMachine::from(*machine)),
+		};
+		Self {
+			snapshots: vec![Snapshot { session, events: 0 }],
+			events: Vec::new(),
+			current: None,
+		}
+	}
+}
+
+/// Appends `event` along with the session it leads to, as a controller does.
+#[cfg(test)]
+async fn append_replayed(store: &mut FileStore, event: RecordedEvent) -> anyhow::Result<()> {
+	let current = store.get_voting_session_at(usize::MAX).await?;
+	store
+		.append_event(event.clone(), current.replay(&[event]))
+		.await
+}
+
 #[tokio::test]
 async fn my_test() {
 	let mut tableau_candidats = BTreeMap::new();
@@ -140,17 +1519,18 @@ async fn my_test() {
 		scores: tableau_candidats,
 		blank_score: Score::default(),
 		invalid_score: Score::default(),
+		profiles: BTreeMap::new(),
 	};
 
 	let voters = AttendenceSheet::default();
 
 	let voting_machine = VotingMachine::new(voters, scoreboard);
 
-	let store = FileStore::create(voting_machine.clone(), "test.json").await;
+	let store = FileStore::create(voting_machine.clone().into(), "test.json").await;
 
-	let stored_machine = store.unwrap().get_voting_machine().await.unwrap();
+	let stored_machine = store.unwrap().get_voting_session().await.unwrap();
 
-	assert_eq!(voting_machine, stored_machine);
+	assert_eq!(VotingSession::from(voting_machine), stored_machine);
 }
 
 #[tokio::test]
@@ -163,17 +1543,445 @@ async fn my_test2() {
 		scores: tableau_candidats,
 		blank_score: Score::default(),
 		invalid_score: Score::default(),
+		profiles: BTreeMap::new(),
 	};
 
 	let voters = AttendenceSheet::default();
 
 	let voting_machine = VotingMachine::new(voters, scoreboard);
 
-	let store = FileStore::create(voting_machine.clone(), "test.json").await;
-	let store2 = FileStore::create(voting_machine.clone(), "test.json").await;
+	let store = FileStore::create(voting_machine.clone().into(), "test.json").await;
+	let store2 = FileStore::create(voting_machine.clone().into(), "test.json").await;
 
-	let stored_machine = store.unwrap().get_voting_machine().await.unwrap();
-	let stored_machine2 = store2.unwrap().get_voting_machine().await.unwrap();
+	let stored_machine = store.unwrap().get_voting_session().await.unwrap();
+	let stored_machine2 = store2.unwrap().get_voting_session().await.unwrap();
 
 	assert_eq!(stored_machine, stored_machine2);
 }
+
+#[tokio::test]
+async fn persists_election_state() {
+	use crate::domain::election_state::ElectionState;
+
+	let filepath = "test_state.json";
+	let scoreboard = Scoreboard::new(vec![Candidate("moi".to_string())]);
+	let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard)
+		.with_state(ElectionState::NotOpen);
+
+	let mut store = FileStore::create(voting_machine.into(), filepath)
+		.await
+		.unwrap();
+	append_replayed(
+		&mut store,
+		RecordedEvent {
+			question: QuestionId::main(),
+			event: VotingEvent::ElectionOpened,
+		},
+	)
+	.await
+	.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	let stored_machine = stored_session.get_question(&QuestionId::main()).unwrap();
+	assert_eq!(ElectionState::Open, stored_machine.get_state());
+}
+
+#[tokio::test]
+async fn persists_every_question() {
+	let filepath = "test_questions.json";
+	let main = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("moi".to_string())]),
+	);
+	let referendum = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![
+			Candidate("oui".to_string()),
+			Candidate("non".to_string()),
+		]),
+	);
+	let session =
+		VotingSession::new(main).with_question(QuestionId("budget".to_string()), referendum);
+
+	let store = FileStore::create(session.clone(), filepath).await.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert_eq!(session, stored_session);
+}
+
+#[tokio::test]
+async fn persists_candidate_changes() {
+	use crate::domain::candidate_change::WithdrawalPolicy;
+
+	let filepath = "test_candidates.json";
+	let mut voting_machine = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("moi".to_string())]),
+	)
+	.with_state(ElectionState::NotOpen);
+	voting_machine.add_candidate(Candidate("toi".to_string()));
+	voting_machine.open();
+	voting_machine.withdraw_candidate(Candidate("moi".to_string()), WithdrawalPolicy::Keep);
+
+	let store = FileStore::create(voting_machine.clone().into(), filepath)
+		.await
+		.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert_eq!(VotingSession::from(voting_machine), stored_session);
+}
+
+#[tokio::test]
+async fn persists_candidate_profiles() {
+	use crate::domain::candidate_profile::CandidateProfile;
+
+	let filepath = "test_profiles.json";
+	let candidate = Candidate("moi".to_string());
+	let profile = CandidateProfile {
+		display_name: "Moi Meme".to_string(),
+		party: Some("Liste".to_string()),
+		statement: Some("Votez pour moi".to_string()),
+		image: Some("/images/moi.png".to_string()),
+	};
+	let scoreboard = Scoreboard::new(vec![candidate.clone()])
+		.with_profiles(BTreeMap::from([(candidate, profile)]));
+	let voting_machine = VotingMachine::new(AttendenceSheet::default(), scoreboard);
+
+	let store = FileStore::create(voting_machine.clone().into(), filepath)
+		.await
+		.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert_eq!(VotingSession::from(voting_machine), stored_session);
+}
+
+#[tokio::test]
+async fn persists_vote_log() {
+	use crate::domain::{
+		vote_log::{ChainVerification, Timestamp, VoteSource},
+		vote_outcome::VoteOutcome,
+	};
+
+	let filepath = "test_vote_log.json";
+	let voter = Voter("moi".to_string());
+	let mut voting_machine = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("toi".to_string())]),
+	);
+	voting_machine.log_vote(
+		Timestamp(1_700_000_000),
+		VoteSource::Udp("127.0.0.1:4000".parse().unwrap()),
+		VoteOutcome::VoteChanged(Box::new(VoteOutcome::InvalidVote(voter.clone()))),
+	);
+	voting_machine.log_vote(
+		Timestamp(1_700_000_001),
+		VoteSource::Web,
+		VoteOutcome::NotProxyHolder(voter.clone(), voter),
+	);
+
+	let store = FileStore::create(voting_machine.clone().into(), filepath)
+		.await
+		.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	let stored_machine = stored_session.get_question(&QuestionId::main()).unwrap();
+	assert_eq!(
+		ChainVerification::Intact(2),
+		stored_machine.get_vote_log().verify()
+	);
+	assert_eq!(VotingSession::from(voting_machine), stored_session);
+}
+
+#[tokio::test]
+async fn persists_history() {
+	use crate::domain::election_state::ElectionState;
+
+	let filepath = "test_history.json";
+	let voting_machine = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("moi".to_string())]),
+	)
+	.with_state(ElectionState::NotOpen);
+	let event = |event| RecordedEvent {
+		question: QuestionId::main(),
+		event,
+	};
+
+	let mut store = FileStore::create(voting_machine.into(), filepath)
+		.await
+		.unwrap();
+	append_replayed(
+		&mut store,
+		event(VotingEvent::CandidateAdded(Candidate("toi".to_string()))),
+	)
+	.await
+	.unwrap();
+	let snapshot = store.get_voting_session_at(1).await.unwrap();
+	store.put_snapshot(snapshot.clone()).await.unwrap();
+	append_replayed(&mut store, event(VotingEvent::ElectionOpened))
+		.await
+		.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	let events = store.get_events().await.unwrap();
+	let latest = store.get_snapshot(1).await.unwrap();
+	let first = store.get_snapshot(0).await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert_eq!(2, events.len());
+	assert_eq!(snapshot, latest);
+	assert_eq!(stored_session, first.replay(&events).session);
+	let stored_machine = stored_session.get_question(&QuestionId::main()).unwrap();
+	assert_eq!(ElectionState::Open, stored_machine.get_state());
+	assert_eq!(2, stored_machine.get_scoreboard().scores.len());
+}
+
+#[tokio::test]
+async fn reads_session_without_history() {
+	let filepath = "test_without_history.json";
+	let session = VotingSession::new(VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("moi".to_string())]),
+	));
+	std::fs::write(
+		filepath,
+		serde_json::to_string(&VotingSessionDAO::from(session.clone())).unwrap(),
+	)
+	.unwrap();
+
+	let store = FileStore::create(VotingSession::default(), filepath)
+		.await
+		.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	let events = store.get_events().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert!(events.is_empty());
+	assert_eq!(session, stored_session);
+}
+
+#[tokio::test]
+async fn normalizes_loaded_names() {
+	use crate::domain::identity::{AccentPolicy, CasePolicy, IdentityPolicy};
+
+	let filepath = "test_normalized_names.json";
+	let mut voters = AttendenceSheet::default();
+	voters.0.insert(Voter(" MALO ".to_string()));
+	let machine = VotingMachine::new(voters, Scoreboard::new(vec![Candidate("Lea".to_string())]))
+		.with_identity(IdentityPolicy {
+			case: CasePolicy::Insensitive,
+			accents: AccentPolicy::Kept,
+		});
+	std::fs::write(
+		filepath,
+		serde_json::to_string(&VotingSessionDAO::from(VotingSession::new(machine))).unwrap(),
+	)
+	.unwrap();
+
+	let store = FileStore::create(VotingSession::default(), filepath)
+		.await
+		.unwrap();
+	let stored_session = store.get_voting_session().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	let stored_machine = stored_session.get_question(&QuestionId::main()).unwrap();
+	assert!(stored_machine
+		.get_voter()
+		.0
+		.contains(&Voter("malo".to_string())));
+	assert!(stored_machine
+		.get_scoreboard()
+		.scores
+		.contains_key(&Candidate("lea".to_string())));
+}
+
+#[tokio::test]
+async fn survives_interrupted_write() {
+	let filepath = "test_interrupted_write.json";
+	let voting_machine = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("moi".to_string())]),
+	);
+	let event = |candidate: &str| RecordedEvent {
+		question: QuestionId::main(),
+		event: VotingEvent::CandidateAdded(Candidate(candidate.to_string())),
+	};
+
+	let mut store = FileStore::create(voting_machine.into(), filepath)
+		.await
+		.unwrap();
+	append_replayed(&mut store, event("toi")).await.unwrap();
+	let written = std::fs::read(filepath).unwrap();
+	// A write cut off before its rename leaves half a file next to the store.
+	std::fs::write(store.temporary_path(), &written[..written.len() / 2]).unwrap();
+	let events_after_crash = store.get_events().await.unwrap();
+	append_replayed(&mut store, event("lui")).await.unwrap();
+	let events = store.get_events().await.unwrap();
+	let leftover = Path::new(&store.temporary_path()).exists();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert_eq!(vec![event("toi")], events_after_crash);
+	assert_eq!(vec![event("toi"), event("lui")], events);
+	assert!(!leftover);
+}
+
+#[tokio::test]
+async fn reports_half_written_file() {
+	let filepath = "test_half_written.json";
+	let store = FileStore::create(VotingSession::default(), filepath)
+		.await
+		.unwrap();
+	let written = std::fs::read(filepath).unwrap();
+	std::fs::write(filepath, &written[..written.len() / 2]).unwrap();
+	let error = store.get_events().await.unwrap_err();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert!(error.downcast_ref::<HalfWrittenFile>().is_some());
+	assert!(error.to_string().contains("interrupted"));
+}
+
+#[tokio::test]
+async fn refuses_store_in_use() {
+	let filepath = "test_store_in_use.json";
+	let store = FileStore::open_locked(VotingSession::default(), filepath, LockMode::Exclusive)
+		.await
+		.unwrap();
+	let second = FileStore::open_locked(VotingSession::default(), filepath, LockMode::Exclusive)
+		.await
+		.map(|_| ())
+		.unwrap_err();
+	let shared = FileStore::open_locked(VotingSession::default(), filepath, LockMode::Shared)
+		.await
+		.map(|_| ())
+		.unwrap_err();
+	drop(store);
+	let reopened = FileStore::open_locked(VotingSession::default(), filepath, LockMode::Exclusive)
+		.await
+		.map(|_| ());
+	std::fs::remove_file(filepath).unwrap();
+	std::fs::remove_file(format!("{filepath}.lock")).unwrap();
+
+	assert!(second.downcast_ref::<StoreInUse>().is_some());
+	assert!(shared.downcast_ref::<StoreInUse>().is_some());
+	assert!(reopened.is_ok());
+}
+
+#[tokio::test]
+async fn shares_store_between_instances() {
+	let filepath = "test_shared_store.json";
+	let voting_machine = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("moi".to_string())]),
+	);
+	let event = |candidate: &str| RecordedEvent {
+		question: QuestionId::main(),
+		event: VotingEvent::CandidateAdded(Candidate(candidate.to_string())),
+	};
+
+	let mut first =
+		FileStore::open_locked(voting_machine.clone().into(), filepath, LockMode::Shared)
+			.await
+			.unwrap();
+	let mut second = FileStore::open_locked(voting_machine.into(), filepath, LockMode::Shared)
+		.await
+		.unwrap();
+	let exclusive = FileStore::open_locked(VotingSession::default(), filepath, LockMode::Exclusive)
+		.await
+		.map(|_| ())
+		.unwrap_err();
+	let mut appends = Vec::new();
+	for (store, candidate) in [(&mut first, "toi"), (&mut second, "lui")] {
+		store.begin_update().await.unwrap();
+		appends.push(append_replayed(store, event(candidate)).await);
+		store.end_update().await.unwrap();
+	}
+	let events = second.get_events().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+	std::fs::remove_file(format!("{filepath}.lock")).unwrap();
+	std::fs::remove_file(format!("{filepath}.update.lock")).unwrap();
+
+	assert!(exclusive.downcast_ref::<StoreInUse>().is_some());
+	assert!(appends.iter().all(Result::is_ok));
+	assert_eq!(vec![event("toi"), event("lui")], events);
+}
+
+#[tokio::test]
+async fn refuses_other_candidates() {
+	let filepath = "test_other_candidates.json";
+	let session = |candidate: &str| {
+		VotingSession::new(VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![Candidate(candidate.to_string())]),
+		))
+	};
+
+	let store = FileStore::open_locked(session("moi"), filepath, LockMode::Exclusive)
+		.await
+		.unwrap();
+	drop(store);
+	let same = FileStore::open_locked(session("moi"), filepath, LockMode::Exclusive)
+		.await
+		.map(|_| ());
+	let other = FileStore::open_locked(session("toi"), filepath, LockMode::Exclusive)
+		.await
+		.map(|_| ())
+		.unwrap_err();
+	std::fs::remove_file(filepath).unwrap();
+	std::fs::remove_file(format!("{filepath}.lock")).unwrap();
+
+	assert!(same.is_ok());
+	assert!(other.downcast_ref::<CandidatesDiffer>().is_some());
+	assert!(other.to_string().contains("[moi]"));
+}
+
+#[tokio::test]
+async fn detects_edited_event() {
+	use crate::domain::vote_log::ChainVerification;
+
+	let filepath = "test_edited_event.json";
+	let voting_machine = VotingMachine::new(
+		AttendenceSheet::default(),
+		Scoreboard::new(vec![Candidate("A".to_string()), Candidate("B".to_string())]),
+	);
+	let ballot = |voter: &str, candidate: &str| {
+		let ballot_paper = BallotPaper::new(
+			Voter(voter.to_string()),
+			Some(Candidate(candidate.to_string())),
+		);
+		RecordedEvent {
+			question: QuestionId::main(),
+			event: VotingEvent::BallotCast {
+				receipt: Receipt::commit(&ballot_paper.voter, &ballot_paper.choice),
+				ballot_paper,
+				timestamp: Timestamp(0),
+				source: VoteSource::Stdio,
+			},
+		}
+	};
+
+	let mut store = FileStore::create(voting_machine.into(), filepath)
+		.await
+		.unwrap();
+	append_replayed(&mut store, ballot("alice", "A"))
+		.await
+		.unwrap();
+	append_replayed(&mut store, ballot("bob", "B"))
+		.await
+		.unwrap();
+	let intact = store.verify_events().await.unwrap();
+	let written = std::fs::read_to_string(filepath).unwrap();
+	std::fs::write(
+		filepath,
+		written.replace(r#""Single":"B""#, r#""Single":"A""#),
+	)
+	.unwrap();
+	let edited = store.verify_events().await.unwrap();
+	std::fs::remove_file(filepath).unwrap();
+
+	assert_eq!(ChainVerification::Intact(2), intact);
+	assert_eq!(ChainVerification::Broken(2), edited);
+}
@@ -1,26 +1,54 @@
+use anyhow::anyhow;
 use async_trait::async_trait;
 
-use crate::{domain::voting_machine::VotingMachine, storage::Storage};
+use crate::{
+	domain::{
+		voting_event::{ChainedEvent, RecordedEvent, Snapshot},
+		voting_session::VotingSession,
+	},
+	storage::Storage,
+};
 
 #[derive(Clone)]
 pub struct MemoryStore {
-	voting_machine: VotingMachine,
+	snapshots: Vec<Snapshot>,
+	events: Vec<ChainedEvent>,
+	current: Option<Snapshot>,
 }
 
 #[async_trait]
 impl Storage for MemoryStore {
-	async fn new(machine: VotingMachine) -> anyhow::Result<Self> {
+	async fn new(session: VotingSession) -> anyhow::Result<Self> {
 		Ok(Self {
-			voting_machine: machine,
+			snapshots: vec![Snapshot { session, events: 0 }],
+			events: Vec::new(),
+			current: None,
 		})
 	}
 
-	async fn get_voting_machine(&self) -> anyhow::Result<VotingMachine> {
-		Ok(self.voting_machine.clone())
+	async fn get_snapshot(&self, events: usize) -> anyhow::Result<Snapshot> {
+		Snapshot::latest(&self.snapshots, self.current.as_ref(), events)
+			.cloned()
+			.ok_or_else(|| anyhow!("no snapshot before event {events}"))
 	}
 
-	async fn put_voting_machine(&mut self, machine: VotingMachine) -> anyhow::Result<()> {
-		self.voting_machine = machine;
+	async fn put_snapshot(&mut self, snapshot: Snapshot) -> anyhow::Result<()> {
+		self.snapshots.push(snapshot);
+		Ok(())
+	}
+
+	async fn get_chain(&self) -> anyhow::Result<Vec<ChainedEvent>> {
+		Ok(self.events.clone())
+	}
+
+	async fn append_event(
+		&mut self,
+		event: RecordedEvent,
+		current: Snapshot,
+	) -> anyhow::Result<()> {
+		let chained = ChainedEvent::following(&self.events, event);
+		self.events.push(chained);
+		self.current = Some(current);
 		Ok(())
 	}
 }
@@ -10,6 +10,7 @@ async fn my_test() {
 		generic_domains::{AttendenceSheet, Candidate, Score},
 		scoreboard::Scoreboard,
 		voting_machine::VotingMachine,
+		voting_session::VotingSession,
 	};
 	use crate::storage::Storage;
 
@@ -21,15 +22,16 @@ async fn my_test() {
 		scores: tableau_candidats,
 		blank_score: Score::default(),
 		invalid_score: Score::default(),
+		profiles: BTreeMap::new(),
 	};
 
 	let voters = AttendenceSheet::default();
 
 	let voting_machine = VotingMachine::new(voters, scoreboard);
 
-	let store = MemoryStore::new(voting_machine.clone()).await;
+	let store = MemoryStore::new(voting_machine.clone().into()).await;
 
-	let stored_machine = store.unwrap().get_voting_machine().await.unwrap();
+	let stored_machine = store.unwrap().get_voting_session().await.unwrap();
 
-	assert_eq!(voting_machine, stored_machine);
+	assert_eq!(VotingSession::from(voting_machine), stored_machine);
 }
@@ -1,22 +1,73 @@
-use std::sync::Arc;
+use std::{num::NonZeroUsize, sync::Arc};
 
+use anyhow::anyhow;
 use serde::Deserialize;
 use tokio::sync::RwLock;
 
 use crate::{
+	clock::{Clock, SystemClock},
 	domain::{
-		ballot_paper::BallotPaper,
-		generic_domains::{Candidate, Voter},
-		vote_outcome::VoteOutcome,
+		ballot_paper::{BallotPaper, Choice},
+		candidate_change::{CandidateOutcome, WithdrawalPolicy},
+		election_state::TransitionOutcome,
+		generic_domains::{Candidate, QuestionId, Voter},
+		identity::IdentityPolicy,
+		proxy::ProxyOutcome,
+		receipt::{Receipt, ReceiptedVote},
+		round::RoundOutcome,
+		vote_log::{ChainVerification, Timestamp, VoteSource},
+		voting_event::{EventOutcome, RecordedEvent, Snapshot, VotingEvent},
 		voting_machine::VotingMachine,
+		voting_session::VotingSession,
 	},
 	storage::Storage,
 };
 
+/// Separates the candidates of a ranked ballot in a `VoteForm`, most preferred first.
+pub const RANKING_SEPARATOR: &str = ",";
+
 #[derive(Deserialize, Debug)]
 pub struct VoteForm {
 	pub voter: String,
 	pub candidate: String,
+	/// Proxy holder casting the ballot, left empty when voting in person.
+	#[serde(default)]
+	pub proxy: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProxyForm {
+	pub voter: String,
+	pub holder: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CandidateForm {
+	pub candidate: String,
+	/// New name when renaming, ignored otherwise.
+	#[serde(default)]
+	pub new_name: String,
+	#[serde(default)]
+	pub policy: PolicyField,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyField {
+	#[default]
+	Keep,
+	Invalidate,
+	Redistribute,
+}
+
+impl From<PolicyField> for WithdrawalPolicy {
+	fn from(value: PolicyField) -> Self {
+		match value {
+			PolicyField::Keep => Self::Keep,
+			PolicyField::Invalidate => Self::Invalidate,
+			PolicyField::Redistribute => Self::Redistribute,
+		}
+	}
 }
 
 impl From<BallotPaper> for VoteForm {
@@ -24,74 +75,379 @@ impl From<BallotPaper> for VoteForm {
 		Self {
 			voter: value.voter.0,
 			candidate: {
-				match value.candidate {
-					Some(value) => value.0,
-					None => String::default(),
+				match value.choice {
+					Choice::Single(Some(value)) => value.0,
+					Choice::Single(None) => String::default(),
+					choice @ (Choice::Ranked(_) | Choice::Approval(_)) => choice
+						.preferences()
+						.into_iter()
+						.map(|candidate| candidate.0)
+						.collect::<Vec<String>>()
+						.join(RANKING_SEPARATOR),
 				}
 			},
+			proxy: value.cast_by.map(|holder| holder.0).unwrap_or_default(),
 		}
 	}
 }
 
 impl From<VoteForm> for BallotPaper {
 	fn from(value: VoteForm) -> Self {
-		if value.candidate.is_empty() {
-			Self {
-				voter: Voter(value.voter),
-				candidate: None,
-			}
+		let voter = Voter(value.voter);
+		let ballot_paper = if value.candidate.trim().is_empty() {
+			Self::new(voter, None)
+		} else if value.candidate.contains(RANKING_SEPARATOR) {
+			let ranking = value
+				.candidate
+				.split(RANKING_SEPARATOR)
+				.map(str::trim)
+				.filter(|candidate| !candidate.is_empty())
+				.map(|candidate| Candidate(candidate.to_string()))
+				.collect();
+			Self::ranked(voter, ranking)
 		} else {
-			Self {
-				voter: Voter(value.voter),
-				candidate: Some(Candidate(value.candidate)),
-			}
+			Self::new(voter, Some(Candidate(value.candidate)))
+		};
+		if value.proxy.is_empty() {
+			ballot_paper
+		} else {
+			ballot_paper.cast_by(Voter(value.proxy))
 		}
 	}
 }
 
+/// Gives access to one question of the stored session, the main one unless
+/// another is picked with `on_question`.
 #[derive(Clone)]
 pub struct VotingController<Store> {
 	store: Arc<RwLock<Store>>,
+	question: QuestionId,
+	/// Where the votes come from, as written to the vote log.
+	source: VoteSource,
+	/// Number of events between two snapshots, none taken when unset. Sessions
+	/// with a secret ballot take none either, as two snapshots would tell the
+	/// ballots cast between them.
+	snapshot_every: Option<NonZeroUsize>,
+	clock: Arc<dyn Clock>,
+}
+
+/// Reports an event that came back with the outcome of another kind of event.
+fn unexpected(outcome: EventOutcome) -> anyhow::Error {
+	anyhow!("unexpected outcome {outcome:?}")
 }
 
 impl<Store: Storage + Clone> VotingController<Store> {
 	pub fn new(store: Store) -> Self {
 		Self {
 			store: Arc::new(RwLock::new(store)),
+			question: QuestionId::main(),
+			source: VoteSource::Stdio,
+			snapshot_every: None,
+			clock: Arc::new(SystemClock),
 		}
 	}
 
-	pub async fn vote(self, vote_forme: VoteForm) -> anyhow::Result<VoteOutcome> {
-		let mut voting_machine = self.store.read().await.get_voting_machine().await?;
-		let outcome = voting_machine.vote(vote_forme.into());
-		self.store
-			.write()
-			.await
-			.put_voting_machine(voting_machine)
+	#[must_use]
+	pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+		self.clock = clock;
+		self
+	}
+
+	#[must_use]
+	pub fn with_snapshot_every(mut self, snapshot_every: Option<NonZeroUsize>) -> Self {
+		self.snapshot_every = snapshot_every;
+		self
+	}
+
+	#[must_use]
+	pub fn on_question(&self, question: QuestionId) -> Self {
+		Self {
+			question,
+			..self.clone()
+		}
+	}
+
+	#[must_use]
+	pub fn from_source(&self, source: VoteSource) -> Self {
+		Self {
+			source,
+			..self.clone()
+		}
+	}
+
+	/// Applies the event `event` builds from the machine of the question, on
+	/// the session rebuilt from the store, and appends it to the store when it
+	/// changed the machine. The scheduled transitions that fell due since the
+	/// last event are recorded first. The store is held for the whole cycle,
+	/// from other processes as well.
+	async fn record(
+		&self,
+		event: impl FnOnce(&VotingMachine) -> VotingEvent + Send,
+		now: Timestamp,
+	) -> anyhow::Result<EventOutcome> {
+		let mut store = self.store.write().await;
+		store.begin_update().await?;
+		let outcome = self.update(&mut store, event, now).await;
+		store.end_update().await?;
+		outcome
+	}
+
+	async fn update(
+		&self,
+		store: &mut Store,
+		event: impl FnOnce(&VotingMachine) -> VotingEvent + Send,
+		now: Timestamp,
+	) -> anyhow::Result<EventOutcome> {
+		let mut snapshot = store.get_voting_session_at(usize::MAX).await?;
+		let machine = snapshot
+			.session
+			.get_question(&self.question)
+			.ok_or_else(|| anyhow!("unknown question {}", self.question))?;
+		let event = event(machine);
+		for due in machine.get_schedule().due(machine.get_state(), now) {
+			self.apply(store, &mut snapshot, due).await?;
+		}
+		self.apply(store, &mut snapshot, event).await
+	}
+
+	async fn apply(
+		&self,
+		store: &mut Store,
+		snapshot: &mut Snapshot,
+		event: VotingEvent,
+	) -> anyhow::Result<EventOutcome> {
+		let machine = snapshot
+			.session
+			.get_question_mut(&self.question)
+			.ok_or_else(|| anyhow!("unknown question {}", self.question))?;
+		let before = machine.clone();
+		let outcome = machine.apply(event.clone());
+		if *machine == before {
+			return Ok(outcome);
+		}
+		let event = if machine.get_secret() {
+			event.without_choice()
+		} else {
+			event
+		};
+		snapshot.events += 1;
+		store
+			.append_event(
+				RecordedEvent {
+					question: self.question.clone(),
+					event,
+				},
+				snapshot.clone(),
+			)
 			.await?;
+		if self
+			.snapshot_every
+			.is_some_and(|every| snapshot.events.is_multiple_of(every.get()))
+			&& !snapshot.session.has_secret_ballot()
+		{
+			store.put_snapshot(snapshot.clone()).await?;
+		}
 		Ok(outcome)
 	}
 
+	/// Casts the ballot, returning its receipt when it was counted, and logs
+	/// the attempt whatever its outcome.
+	pub async fn vote(self, vote_forme: VoteForm) -> anyhow::Result<ReceiptedVote> {
+		let timestamp = self.clock.now();
+		let source = self.source;
+		let event = |machine: &VotingMachine| {
+			let ballot_paper = BallotPaper::from(vote_forme).normalized(machine.get_identity());
+			VotingEvent::BallotCast {
+				receipt: Receipt::commit(&ballot_paper.voter, &ballot_paper.choice),
+				ballot_paper,
+				timestamp,
+				source,
+			}
+		};
+		match self.record(event, timestamp).await? {
+			EventOutcome::Vote(vote) => Ok(vote),
+			outcome => Err(unexpected(outcome)),
+		}
+	}
+
+	pub async fn register_proxy(self, proxy_form: ProxyForm) -> anyhow::Result<ProxyOutcome> {
+		let event = |machine: &VotingMachine| {
+			let identity = machine.get_identity();
+			VotingEvent::ProxyRegistered(
+				identity.voter(Voter(proxy_form.voter)),
+				identity.voter(Voter(proxy_form.holder)),
+			)
+		};
+		match self.record(event, self.clock.now()).await? {
+			EventOutcome::Proxy(outcome) => Ok(outcome),
+			outcome => Err(unexpected(outcome)),
+		}
+	}
+
+	pub async fn close_round(self) -> anyhow::Result<RoundOutcome> {
+		match self
+			.record(|_| VotingEvent::RoundClosed, self.clock.now())
+			.await?
+		{
+			EventOutcome::Round(outcome) => Ok(outcome),
+			outcome => Err(unexpected(outcome)),
+		}
+	}
+
+	pub async fn open_election(self) -> anyhow::Result<TransitionOutcome> {
+		match self
+			.record(|_| VotingEvent::ElectionOpened, self.clock.now())
+			.await?
+		{
+			EventOutcome::Transition(outcome) => Ok(outcome),
+			outcome => Err(unexpected(outcome)),
+		}
+	}
+
+	pub async fn close_election(self) -> anyhow::Result<TransitionOutcome> {
+		match self
+			.record(|_| VotingEvent::ElectionClosed, self.clock.now())
+			.await?
+		{
+			EventOutcome::Transition(outcome) => Ok(outcome),
+			outcome => Err(unexpected(outcome)),
+		}
+	}
+
+	pub async fn add_candidate(
+		self,
+		candidate_form: CandidateForm,
+	) -> anyhow::Result<CandidateOutcome> {
+		let event = |identity: &IdentityPolicy| {
+			VotingEvent::CandidateAdded(identity.candidate(Candidate(candidate_form.candidate)))
+		};
+		self.record_candidate_change(event).await
+	}
+
+	pub async fn withdraw_candidate(
+		self,
+		candidate_form: CandidateForm,
+	) -> anyhow::Result<CandidateOutcome> {
+		let event = |identity: &IdentityPolicy| {
+			VotingEvent::CandidateWithdrawn(
+				identity.candidate(Candidate(candidate_form.candidate)),
+				candidate_form.policy.into(),
+			)
+		};
+		self.record_candidate_change(event).await
+	}
+
+	pub async fn rename_candidate(
+		self,
+		candidate_form: CandidateForm,
+	) -> anyhow::Result<CandidateOutcome> {
+		let event = |identity: &IdentityPolicy| {
+			VotingEvent::CandidateRenamed(
+				identity.candidate(Candidate(candidate_form.candidate)),
+				identity.candidate(Candidate(candidate_form.new_name)),
+			)
+		};
+		self.record_candidate_change(event).await
+	}
+
+	/// Records the candidate change `event` builds from the names, written the
+	/// way the question says.
+	async fn record_candidate_change(
+		&self,
+		event: impl FnOnce(&IdentityPolicy) -> VotingEvent + Send,
+	) -> anyhow::Result<CandidateOutcome> {
+		let event = |machine: &VotingMachine| event(machine.get_identity());
+		match self.record(event, self.clock.now()).await? {
+			EventOutcome::Candidate(outcome) => Ok(outcome),
+			outcome => Err(unexpected(outcome)),
+		}
+	}
+
+	#[must_use]
+	pub const fn get_question(&self) -> &QuestionId {
+		&self.question
+	}
+
+	#[must_use]
+	pub fn now(&self) -> Timestamp {
+		self.clock.now()
+	}
+
+	/// Returns every recorded event, of every question, oldest first.
+	pub async fn get_history(&self) -> anyhow::Result<Vec<RecordedEvent>> {
+		self.store.read().await.get_events().await
+	}
+
+	/// Checks every recorded event, of every question, against the hash
+	/// stored with it.
+	pub async fn verify_history(&self) -> anyhow::Result<ChainVerification> {
+		self.store.read().await.verify_events().await
+	}
+
+	/// Returns the machine of the question as it stood after the first `events`
+	/// events, or `None` before the last event of a secret question: its events
+	/// keep no choice, and two past counts would tell the ballot cast between them.
+	pub async fn get_voting_machine_at(
+		&self,
+		events: usize,
+	) -> anyhow::Result<Option<VotingMachine>> {
+		let store = self.store.read().await;
+		let snapshot = store.get_voting_session_at(events).await?;
+		let machine = snapshot
+			.session
+			.get_question(&self.question)
+			.cloned()
+			.ok_or_else(|| anyhow!("unknown question {}", self.question))?;
+		let latest = store.get_chain().await?.len();
+		Ok((!machine.get_secret() || snapshot.events >= latest).then_some(machine))
+	}
+
 	pub async fn get_voting_machine(&self) -> anyhow::Result<VotingMachine> {
-		self.store.read().await.get_voting_machine().await
+		self.get_voting_session()
+			.await?
+			.get_question(&self.question)
+			.cloned()
+			.ok_or_else(|| anyhow!("unknown question {}", self.question))
+	}
+
+	/// Returns the session as it stands now, with the scheduled transitions
+	/// that fell due since the last event, which the next event records.
+	pub async fn get_voting_session(&self) -> anyhow::Result<VotingSession> {
+		let mut session = self.store.read().await.get_voting_session().await?;
+		session.run_due(self.clock.now());
+		Ok(session)
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use std::{collections::BTreeMap, process::exit};
+	use std::{
+		collections::BTreeMap,
+		num::NonZeroUsize,
+		process::exit,
+		sync::{
+			atomic::{AtomicU64, Ordering},
+			Arc,
+		},
+	};
 
 	use crate::{
+		clock::Clock,
 		domain::{
 			ballot_paper::BallotPaper,
-			generic_domains::{AttendenceSheet, Candidate, Score, Voter},
+			election_state::{ElectionState, TransitionOutcome},
+			generic_domains::{AttendenceSheet, Candidate, QuestionId, Score, Voter},
+			identity::{AccentPolicy, CasePolicy, IdentityPolicy},
+			schedule::Schedule,
 			scoreboard::Scoreboard,
+			vote_log::Timestamp,
 			vote_outcome::VoteOutcome,
+			voting_event::VotingEvent,
 			voting_machine::VotingMachine,
 		},
 		storage::Storage,
 		storages::memory::MemoryStore,
-		use_cases::VotingController,
+		use_cases::{VoteForm, VotingController},
 	};
 
 	#[tokio::test]
@@ -104,13 +460,14 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = match MemoryStore::new(voting_machine).await {
+		let memory = match MemoryStore::new(voting_machine.into()).await {
 			Ok(memory) => memory,
 			Err(e) => {
 				println!("error : {e}");
@@ -121,7 +478,7 @@ mod tests {
 		let voter = Voter("Malo".to_string());
 		let ballot_paper = BallotPaper::new(voter.clone(), Some(candidate.clone()));
 		let outcome = match controller.clone().vote(ballot_paper.into()).await {
-			Ok(outcome) => outcome,
+			Ok(vote) => vote.outcome,
 			Err(e) => {
 				println!("error : {e}");
 				exit(1)
@@ -135,6 +492,7 @@ mod tests {
 			scores: correct_scores,
 			blank_score: Score(0),
 			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
 		};
 		correct_voters.0.insert(voter.clone());
 		let voting_machine = match controller.get_voting_machine().await {
@@ -160,6 +518,7 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let mut voters = AttendenceSheet::default();
@@ -167,7 +526,7 @@ mod tests {
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = match MemoryStore::new(voting_machine).await {
+		let memory = match MemoryStore::new(voting_machine.into()).await {
 			Ok(memory) => memory,
 			Err(e) => {
 				println!("error : {e}");
@@ -177,7 +536,7 @@ mod tests {
 		let controller = VotingController::new(memory);
 		let ballot_paper = BallotPaper::new(voter.clone(), Some(candidate.clone()));
 		let outcome = match controller.clone().vote(ballot_paper.into()).await {
-			Ok(outcome) => outcome,
+			Ok(vote) => vote.outcome,
 			Err(e) => {
 				println!("error : {e}");
 				exit(1)
@@ -191,6 +550,7 @@ mod tests {
 			scores: correct_scores,
 			blank_score: Score(0),
 			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
 		};
 		correct_voters.0.insert(voter.clone());
 		let voting_machine = match controller.get_voting_machine().await {
@@ -215,13 +575,14 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = match MemoryStore::new(voting_machine).await {
+		let memory = match MemoryStore::new(voting_machine.into()).await {
 			Ok(memory) => memory,
 			Err(e) => {
 				println!("error : {e}");
@@ -231,7 +592,7 @@ mod tests {
 		let controller = VotingController::new(memory);
 		let ballot_paper = BallotPaper::new(voter.clone(), Some(candidate.clone()));
 		let outcome = match controller.clone().vote(ballot_paper.into()).await {
-			Ok(outcome) => outcome,
+			Ok(vote) => vote.outcome,
 			Err(e) => {
 				println!("error : {e}");
 				exit(1)
@@ -244,6 +605,7 @@ mod tests {
 			scores: correct_scores,
 			blank_score: Score(0),
 			invalid_score: Score(1),
+			profiles: BTreeMap::new(),
 		};
 		correct_voters.0.insert(voter.clone());
 		let voting_machine = match controller.get_voting_machine().await {
@@ -269,13 +631,14 @@ mod tests {
 			scores: tableau_candidats,
 			blank_score: Score::default(),
 			invalid_score: Score::default(),
+			profiles: BTreeMap::new(),
 		};
 
 		let voters = AttendenceSheet::default();
 
 		let voting_machine = VotingMachine::new(voters, scoreboard);
 
-		let memory = match MemoryStore::new(voting_machine).await {
+		let memory = match MemoryStore::new(voting_machine.into()).await {
 			Ok(memory) => memory,
 			Err(e) => {
 				println!("error : {e}");
@@ -285,7 +648,7 @@ mod tests {
 		let controller = VotingController::new(memory);
 		let ballot_paper = BallotPaper::new(voter.clone(), None);
 		let outcome = match controller.clone().vote(ballot_paper.into()).await {
-			Ok(outcome) => outcome,
+			Ok(vote) => vote.outcome,
 			Err(e) => {
 				println!("error : {e}");
 				exit(1)
@@ -299,6 +662,7 @@ mod tests {
 			scores: correct_scores,
 			blank_score: Score(1),
 			invalid_score: Score(0),
+			profiles: BTreeMap::new(),
 		};
 		correct_voters.0.insert(voter.clone());
 		let voting_machine = match controller.get_voting_machine().await {
@@ -312,4 +676,218 @@ mod tests {
 		assert_eq!(correct_outcome, outcome);
 		assert_eq!(&correct_scoreboard, voting_machine.get_scoreboard());
 	}
+
+	#[tokio::test]
+	async fn replays_recorded_events() {
+		let candidate = Candidate("MacOS".to_string());
+		let voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![candidate.clone()]),
+		);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory).with_snapshot_every(NonZeroUsize::new(2));
+
+		for voter in ["Malo", "Lea", "Ines"] {
+			let ballot_paper = BallotPaper::new(Voter(voter.to_string()), Some(candidate.clone()));
+			controller.clone().vote(ballot_paper.into()).await.unwrap();
+		}
+		assert_eq!(
+			TransitionOutcome::InvalidTransition(ElectionState::Open),
+			controller.clone().open_election().await.unwrap()
+		);
+
+		let history = controller.get_history().await.unwrap();
+		assert_eq!(3, history.len());
+		assert!(history
+			.iter()
+			.all(|recorded| recorded.question == QuestionId::main()
+				&& matches!(recorded.event, VotingEvent::BallotCast { .. })));
+
+		let store = controller.store.read().await;
+		assert_eq!(2, store.get_snapshot(2).await.unwrap().events);
+		assert_eq!(3, store.get_snapshot(usize::MAX).await.unwrap().events);
+		assert_eq!(
+			Score(1),
+			controller
+				.get_voting_machine_at(1)
+				.await
+				.unwrap()
+				.unwrap()
+				.get_scoreboard()
+				.scores[&candidate]
+		);
+		let replayed = store
+			.get_snapshot(0)
+			.await
+			.unwrap()
+			.replay(&history)
+			.session;
+		assert_eq!(store.get_voting_session().await.unwrap(), replayed);
+		assert_eq!(
+			Score(3),
+			replayed
+				.get_question(&QuestionId::main())
+				.unwrap()
+				.get_scoreboard()
+				.scores[&candidate]
+		);
+	}
+
+	#[tokio::test]
+	async fn records_check_ins_of_secret_ballots() {
+		let candidates = vec![Candidate("A".to_string()), Candidate("B".to_string())];
+		let voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(candidates.clone()),
+		)
+		.with_secret(true);
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory).with_snapshot_every(NonZeroUsize::new(1));
+
+		for (voter, candidate) in [("alice", &candidates[0]), ("bob", &candidates[1])] {
+			let ballot_paper = BallotPaper::new(Voter(voter.to_string()), Some(candidate.clone()));
+			controller.clone().vote(ballot_paper.into()).await.unwrap();
+		}
+
+		let history = controller.get_history().await.unwrap();
+		assert!(history
+			.iter()
+			.all(|recorded| matches!(recorded.event, VotingEvent::CheckedIn { .. })));
+		let store = controller.store.read().await;
+		assert_eq!(0, store.get_snapshot(1).await.unwrap().events);
+		let voting_machine = controller.get_voting_machine().await.unwrap();
+		assert_eq!(2, voting_machine.get_voter().0.len());
+		assert!(candidates
+			.iter()
+			.all(|candidate| voting_machine.get_scoreboard().scores[candidate] == Score(1)));
+		assert_eq!(2, voting_machine.get_tally().0.len());
+	}
+
+	/// Clock the test moves forward by hand.
+	#[derive(Default)]
+	struct FakeClock(AtomicU64);
+
+	impl Clock for FakeClock {
+		fn now(&self) -> Timestamp {
+			Timestamp(self.0.load(Ordering::SeqCst))
+		}
+	}
+
+	#[tokio::test]
+	async fn scheduled_window() {
+		let candidate = Candidate("MacOS".to_string());
+		let voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![candidate.clone()]),
+		)
+		.with_state(ElectionState::NotOpen)
+		.with_schedule(Schedule {
+			opens: Some(Timestamp(100)),
+			closes: Some(Timestamp(200)),
+		});
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let clock = Arc::new(FakeClock::default());
+		let controller = VotingController::new(memory).with_clock(clock.clone());
+		let vote = |voter: &str| {
+			let ballot_paper = BallotPaper::new(Voter(voter.to_string()), Some(candidate.clone()));
+			controller.clone().vote(ballot_paper.into())
+		};
+
+		clock.0.store(50, Ordering::SeqCst);
+		assert_eq!(
+			VoteOutcome::ElectionNotOpen(Voter("Malo".to_string())),
+			vote("Malo").await.unwrap().outcome
+		);
+
+		clock.0.store(150, Ordering::SeqCst);
+		assert_eq!(
+			VoteOutcome::AcceptedVote(Voter("Lea".to_string()), candidate.clone()),
+			vote("Lea").await.unwrap().outcome
+		);
+		assert_eq!(
+			ElectionState::Open,
+			controller.get_voting_machine().await.unwrap().get_state()
+		);
+
+		clock.0.store(200, Ordering::SeqCst);
+		assert_eq!(
+			ElectionState::Closed,
+			controller.get_voting_machine().await.unwrap().get_state()
+		);
+		assert_eq!(3, controller.get_history().await.unwrap().len());
+		let late = vote("Ines").await.unwrap();
+		assert_eq!(
+			VoteOutcome::DeadlinePassed(Voter("Ines".to_string())),
+			late.outcome
+		);
+		assert_eq!(None, late.receipt);
+
+		let machine = controller.get_voting_machine().await.unwrap();
+		assert_eq!(ElectionState::Closed, machine.get_state());
+		assert_eq!(Score(1), machine.get_scoreboard().scores[&candidate]);
+		let events: Vec<VotingEvent> = controller
+			.get_history()
+			.await
+			.unwrap()
+			.into_iter()
+			.map(|recorded| recorded.event)
+			.collect();
+		assert!(matches!(
+			events.as_slice(),
+			[
+				VotingEvent::BallotCast { .. },
+				VotingEvent::ElectionOpened,
+				VotingEvent::BallotCast { .. },
+				VotingEvent::ElectionClosed,
+				VotingEvent::BallotCast { .. },
+			]
+		));
+	}
+
+	#[tokio::test]
+	async fn normalizes_identities() {
+		let voting_machine = VotingMachine::new(
+			AttendenceSheet::default(),
+			Scoreboard::new(vec![Candidate("macron".to_string())]),
+		)
+		.with_identity(IdentityPolicy {
+			case: CasePolicy::Insensitive,
+			accents: AccentPolicy::Stripped,
+		});
+		let memory = MemoryStore::new(voting_machine.into()).await.unwrap();
+		let controller = VotingController::new(memory);
+		let vote = |voter: &str, candidate: &str| VoteForm {
+			voter: voter.to_string(),
+			candidate: candidate.to_string(),
+			proxy: String::new(),
+		};
+
+		assert_eq!(
+			VoteOutcome::AcceptedVote(Voter("lea".to_string()), Candidate("macron".to_string())),
+			controller
+				.clone()
+				.vote(vote("L\u{e9}a", " MACRON "))
+				.await
+				.unwrap()
+				.outcome
+		);
+		assert_eq!(
+			VoteOutcome::HasAlreadyVoted(Voter("lea".to_string())),
+			controller
+				.clone()
+				.vote(vote(" lea ", "macron"))
+				.await
+				.unwrap()
+				.outcome
+		);
+		assert_eq!(
+			VoteOutcome::BlankVote(Voter("malo".to_string())),
+			controller
+				.clone()
+				.vote(vote("Malo", "  "))
+				.await
+				.unwrap()
+				.outcome
+		);
+	}
 }